@@ -1,4 +1,12 @@
-use clap::{Args, Parser, Subcommand};
+use clap::{
+    Args, Parser, Subcommand, ValueEnum,
+    builder::{PossibleValuesParser, RangedU64ValueParser, TypedValueParser},
+};
+
+use crate::{
+    convert::{AnyConverter, exact::parse_decimal},
+    format::{Formatter, Notation, Reading, Rounding, implied_rounding},
+};
 
 /// Simple unit converter
 #[derive(Parser)]
@@ -13,28 +21,159 @@ pub struct Cli {
 
 #[derive(Subcommand)]
 pub enum Commands {
+    /// Convert a value from one unit into others
     Convert(ConvertArgs),
+    /// Convert an expression such as "5 ft 11 in -> cm"
     Expression(ExpressionArgs),
+    /// Show a unit's exact definition and its source
+    Info(InfoArgs),
     Interactive,
     List(ListArgs),
 }
 
+/// How values are represented while converting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum PrecisionMode {
+    /// 64-bit floating point
+    #[default]
+    Float,
+    /// Exact decimal arithmetic, with the value read as written
+    Decimal,
+}
+
 #[derive(Args)]
 pub struct ConvertArgs {
-    #[arg(help = "Value to convert")]
-    pub value: f64,
+    #[arg(help = "Value to convert", value_parser = parse_number)]
+    pub value: String,
 
     #[arg(short, long, help = "Source unit (e.g. m, ft, kg)")]
     pub from: String,
 
-    #[arg(short, long, help = "Target unit (e.g. m, ft, kg)")]
-    pub to: String,
+    #[arg(
+        short,
+        long,
+        required_unless_present = "all",
+        value_delimiter = ',',
+        help = "Target unit (e.g. m, ft, kg), repeated or comma-separated for several"
+    )]
+    pub to: Vec<String>,
+
+    #[arg(
+        long,
+        conflicts_with = "to",
+        help = "Convert into every unit of the value's kind"
+    )]
+    pub all: bool,
+
+    #[command(flatten)]
+    pub options: ConversionOptions,
 }
 
 #[derive(Args)]
 pub struct ExpressionArgs {
     #[arg(short, long)]
     pub expr: String,
+
+    #[command(flatten)]
+    pub options: ConversionOptions,
+}
+
+#[derive(Args)]
+pub struct InfoArgs {
+    #[arg(help = "Unit to describe (e.g. mi, kN, °F)")]
+    pub unit: String,
+}
+
+// Options shared by the subcommands that convert a value. A doc comment here would become
+// the about text of each subcommand that flattens it.
+#[derive(Args)]
+pub struct ConversionOptions {
+    #[arg(long, help = "Allow temperatures below absolute zero")]
+    pub allow_below_absolute_zero: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        help = "Number representation to convert with"
+    )]
+    pub precision_mode: PrecisionMode,
+
+    #[arg(
+        long,
+        value_parser = RangedU64ValueParser::<usize>::new().range(1..),
+        conflicts_with = "decimals",
+        help = "Round the result to this many significant figures"
+    )]
+    pub sig_figs: Option<usize>,
+
+    #[arg(long, help = "Round the result to this many decimal places")]
+    pub decimals: Option<usize>,
+
+    #[arg(long, value_enum, default_value_t, help = "How to lay out numbers")]
+    pub notation: Notation,
+
+    #[arg(long, help = "Group digits in thousands, e.g. 1,234,567.89")]
+    pub group_digits: bool,
+
+    #[arg(
+        long,
+        default_value_t = '.',
+        value_parser = PossibleValuesParser::new([".", ","])
+            .map(|separator| if separator == "," { ',' } else { '.' }),
+        help = "Character to write before the fraction digits, with digits grouped by the other"
+    )]
+    pub decimal_separator: char,
+}
+
+impl ConversionOptions {
+    /// The formatter for a conversion's result, rounded as [`Self::rounding`] decides.
+    pub fn formatter(
+        &self,
+        converter: &AnyConverter,
+        value: Reading,
+        from: &str,
+        to: &str,
+    ) -> Formatter {
+        Formatter {
+            rounding: self.rounding(converter, value, from, to),
+            ..self.plain_formatter()
+        }
+    }
+
+    /// The formatter for values that are not rounded, such as the input.
+    pub fn plain_formatter(&self) -> Formatter {
+        // Digits are grouped with whichever of `,` and `.` is not the decimal separator
+        let group_separator = match self.decimal_separator {
+            ',' => '.',
+            _ => ',',
+        };
+        Formatter {
+            notation: self.notation,
+            rounding: None,
+            group_separator: self.group_digits.then_some(group_separator),
+            decimal_separator: self.decimal_separator,
+        }
+    }
+
+    /// The rounding asked for on the command line. Without one, float results keep the
+    /// precision the value was written with, and decimal results are exact.
+    pub fn rounding(
+        &self,
+        converter: &AnyConverter,
+        value: Reading,
+        from: &str,
+        to: &str,
+    ) -> Option<Rounding> {
+        match (self.sig_figs, self.decimals) {
+            (Some(figures), _) => Some(Rounding::SignificantFigures(figures)),
+            (None, Some(places)) => Some(Rounding::Decimals(places)),
+            (None, None) => match self.precision_mode {
+                PrecisionMode::Float => Some(implied_rounding(converter, value, from, to)),
+                PrecisionMode::Decimal => None,
+            },
+        }
+    }
 }
 
 #[derive(Args)]
@@ -42,3 +181,13 @@ pub struct ListArgs {
     #[arg(short, long)]
     pub category: Option<String>,
 }
+
+/// Checks the value is a number but keeps it as written, so that decimal mode can read it
+/// exactly. Anything accepted here also parses as an `f64` for float mode, which then refuses
+/// values too large for it.
+fn parse_number(value: &str) -> Result<String, String> {
+    let value = value.trim();
+    parse_decimal(value)
+        .map(|_| value.to_string())
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,195 @@
+use std::str::FromStr;
+
+use crate::error::ConvertError;
+
+use super::{
+    AnyUnit, dimension::Dimension, distance::DistanceUnit, exact::from_f64, mass::MassUnit,
+};
+
+/// A family of units that are used together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitSystem {
+    Metric,
+    Imperial,
+}
+
+impl UnitSystem {
+    /// The system a unit belongs to, if it belongs to only one. Units of time are shared.
+    pub fn of(unit: &AnyUnit) -> Option<UnitSystem> {
+        use DistanceUnit::*;
+        use MassUnit::*;
+        match unit {
+            AnyUnit::Distance(u) => match u.unit {
+                Foot | Mile | Inch | Yard | Fathom | Furlong | Chain | Rod | Hand | Thou
+                | UsSurveyFoot | UsSurveyMile => Some(UnitSystem::Imperial),
+                _ => Some(UnitSystem::Metric),
+            },
+            AnyUnit::Mass(u) => match u.unit {
+                Pound | Stone | Ounce | ShortTon | LongTon | Grain | TroyOunce | TroyPound
+                | Pennyweight | Slug => Some(UnitSystem::Imperial),
+                _ => Some(UnitSystem::Metric),
+            },
+            _ => None,
+        }
+    }
+
+    /// The units to choose from, smallest first.
+    fn candidates(&self, dimension: Dimension) -> &'static [&'static str] {
+        match (dimension, self) {
+            (Dimension::LENGTH, UnitSystem::Metric) => &["nm", "µm", "mm", "cm", "m", "km"],
+            (Dimension::LENGTH, UnitSystem::Imperial) => &["in", "ft", "mi"],
+            (Dimension::MASS, UnitSystem::Metric) => &["µg", "mg", "g", "kg", "t"],
+            (Dimension::MASS, UnitSystem::Imperial) => &["gr", "oz", "lb", "ton"],
+            (Dimension::TIME, _) => &["ns", "µs", "ms", "s", "min", "h", "d", "wk", "yr"],
+            _ => &[],
+        }
+    }
+}
+
+impl FromStr for UnitSystem {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "metric" | "si" => Ok(UnitSystem::Metric),
+            "imperial" | "us" | "customary" => Ok(UnitSystem::Imperial),
+            _ => Err(ConvertError::ParseError(format!(
+                "Unknown unit system '{}'",
+                s
+            ))),
+        }
+    }
+}
+
+/// A target asking for whichever unit reads best rather than a particular one, written
+/// `auto` or `best` and optionally followed by the system to pick from, as in `auto imperial`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BestUnit {
+    /// The system to pick from, or `None` for the system of the unit converted from.
+    pub system: Option<UnitSystem>,
+}
+
+impl BestUnit {
+    /// Picks the largest unit in which the value is still at least 1, so `0.000032 km` is
+    /// best read in `cm` and `86400 s` in `d`. Values smaller than every unit use the smallest.
+    /// Units with nothing to choose between, such as temperatures and compound units, are kept.
+    pub fn choose(&self, value: f64, from: &str) -> Result<String, ConvertError> {
+        let Ok(unit) = AnyUnit::from_str(from) else {
+            return Ok(from.to_string());
+        };
+        let system = self
+            .system
+            .or_else(|| UnitSystem::of(&unit))
+            .unwrap_or(UnitSystem::Metric);
+        let candidates = system
+            .candidates(unit.dimension())
+            .iter()
+            .map(|candidate| AnyUnit::from_str(candidate))
+            .collect::<Result<Vec<_>, _>>()?;
+        let Some(smallest) = candidates.first() else {
+            return Ok(from.to_string());
+        };
+        if value == 0.0 || !value.is_finite() {
+            return Ok(from.to_string());
+        }
+
+        let size = from_f64(value.abs())? * unit.exact_factor();
+        let best = candidates
+            .iter()
+            .rev()
+            .find(|candidate| size >= candidate.exact_factor())
+            .unwrap_or(smallest);
+        Ok(best.to_string())
+    }
+}
+
+impl FromStr for BestUnit {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.trim().to_lowercase();
+        let (keyword, system) = lower
+            .split_once([' ', ':'])
+            .map_or((lower.as_str(), None), |(keyword, system)| {
+                (keyword, Some(system.trim()))
+            });
+        if keyword != "auto" && keyword != "best" {
+            return Err(ConvertError::InvalidUnit(s.to_string()));
+        }
+        Ok(BestUnit {
+            system: system.map(UnitSystem::from_str).transpose()?,
+        })
+    }
+}
+
+/// Replaces an `auto` or `best` target with the unit it picks for the value. Any other target
+/// is returned as it is.
+pub fn resolve_target(value: f64, from: &str, to: &str) -> Result<String, ConvertError> {
+    match BestUnit::from_str(to) {
+        Ok(best) => best.choose(value, from),
+        Err(ConvertError::InvalidUnit(_)) => Ok(to.to_string()),
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::error::ConvertError;
+
+    use super::{BestUnit, UnitSystem, resolve_target};
+
+    #[test]
+    fn test_parse_best_unit() {
+        assert_eq!(BestUnit::from_str("auto"), Ok(BestUnit { system: None }));
+        assert_eq!(
+            BestUnit::from_str("best imperial"),
+            Ok(BestUnit {
+                system: Some(UnitSystem::Imperial)
+            })
+        );
+        assert_eq!(
+            BestUnit::from_str("Auto:Metric"),
+            Ok(BestUnit {
+                system: Some(UnitSystem::Metric)
+            })
+        );
+        assert!(matches!(
+            BestUnit::from_str("auto nautical"),
+            Err(ConvertError::ParseError(_))
+        ));
+        assert!(matches!(
+            BestUnit::from_str("m"),
+            Err(ConvertError::InvalidUnit(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolve_target() {
+        let tests = [
+            (0.000032, "km", "auto", "cm"),
+            (86400.0, "s", "best", "d"),
+            (1500.0, "m", "auto", "km"),
+            (0.5, "nm", "auto", "nm"),
+            (-2500.0, "g", "auto", "kg"),
+            (18.0, "in", "auto", "ft"),
+            (6000.0, "ft", "auto", "mi"),
+            (2.0, "km", "auto imperial", "mi"),
+            (24.0, "oz", "auto metric", "g"),
+            (100.0, "c", "auto", "c"),
+            (10.0, "m/s", "auto", "m/s"),
+            (3.0, "km", "ft", "ft"),
+        ];
+        for (value, from, to, expected) in tests {
+            assert_eq!(
+                resolve_target(value, from, to),
+                Ok(expected.to_string()),
+                "{} {} -> {}",
+                value,
+                from,
+                to
+            );
+        }
+    }
+}
@@ -0,0 +1,579 @@
+use std::{
+    fmt::Display,
+    iter::Peekable,
+    ops::{Div, Mul},
+    str::{Chars, FromStr},
+};
+
+use num_rational::BigRational;
+use strum::IntoEnumIterator;
+
+use crate::error::ConvertError;
+
+use super::{AnyUnit, UnitConverter, derived::DerivedUnit, dimension::Dimension, exact::scale};
+
+/// Converts between any two units of equal dimension, however they are composed.
+pub struct CompoundConverter {
+    dimension: Dimension,
+}
+
+/// A unit built from other units by multiplication, division and integer powers,
+/// e.g. `m/s`, `kg·m/s²` or `W/(m·K)`.
+///
+/// Terms are combined left to right, so `W/m·K` means `(W/m)·K`; use parentheses for
+/// `W/(m·K)`. A single unit parses as a compound unit with one term.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CompoundUnit {
+    terms: Vec<(AnyUnit, i8)>,
+}
+
+impl CompoundConverter {
+    pub fn new(dimension: Dimension) -> Self {
+        CompoundConverter { dimension }
+    }
+
+    pub fn get_unit_string(&self, unit_str: &str) -> String {
+        if let Ok(unit) = CompoundUnit::from_str(unit_str) {
+            unit.to_string()
+        } else {
+            unit_str.to_string()
+        }
+    }
+
+    /// Converts an exact rational value with a single exact multiplication.
+    pub fn convert_exact(
+        &self,
+        value: &BigRational,
+        from: &str,
+        to: &str,
+    ) -> Result<BigRational, ConvertError> {
+        let from_unit = CompoundUnit::from_str(from)?;
+        let to_unit = CompoundUnit::from_str(to)?;
+        Ok(value * Self::factor(&from_unit, from, &to_unit, to)?)
+    }
+
+    /// The exact ratio between two units, composed before any value is touched.
+    fn factor(
+        from_unit: &CompoundUnit,
+        from: &str,
+        to_unit: &CompoundUnit,
+        to: &str,
+    ) -> Result<BigRational, ConvertError> {
+        if from_unit.dimension() != to_unit.dimension() {
+            return Err(ConvertError::IncompatibleDimensions {
+                from: from.to_string(),
+                from_dimension: from_unit.dimension(),
+                to: to.to_string(),
+                to_dimension: to_unit.dimension(),
+            });
+        }
+        Ok(from_unit.exact_factor() / to_unit.exact_factor())
+    }
+}
+
+impl UnitConverter for CompoundConverter {
+    fn convert(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError> {
+        let from_unit = CompoundUnit::from_str(from)?;
+        let to_unit = CompoundUnit::from_str(to)?;
+
+        let factor = Self::factor(&from_unit, from, &to_unit, to)?;
+        Ok(scale(value, &factor))
+    }
+
+    fn dimension(&self) -> Dimension {
+        self.dimension
+    }
+
+    fn supported_units(&self) -> Vec<String> {
+        DerivedUnit::iter()
+            .filter(|unit| unit.dimension() == self.dimension)
+            .map(|unit| unit.to_string())
+            .collect()
+    }
+}
+
+impl CompoundUnit {
+    pub fn terms(&self) -> &[(AnyUnit, i8)] {
+        &self.terms
+    }
+
+    /// The unit itself, if it is a single unit raised to the first power.
+    pub fn as_simple(&self) -> Option<AnyUnit> {
+        match self.terms.as_slice() {
+            [(unit, 1)] => Some(*unit),
+            _ => None,
+        }
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        self.checked_dimension()
+            .expect("unit exponents are checked when the unit is built")
+    }
+
+    fn checked_dimension(&self) -> Option<Dimension> {
+        self.terms
+            .iter()
+            .try_fold(Dimension::DIMENSIONLESS, |dimension, (unit, power)| {
+                dimension.checked_mul(unit.dimension().checked_powi(*power)?)
+            })
+    }
+
+    pub fn exact_factor(&self) -> BigRational {
+        self.terms
+            .iter()
+            .map(|(unit, power)| unit.exact_factor().pow(*power as i32))
+            .product()
+    }
+
+    /// Raises the unit to a power, failing if an exponent of the unit or of its dimension
+    /// leaves the range of `i8`.
+    pub fn powi(&self, power: i8) -> Result<CompoundUnit, ConvertError> {
+        let mut terms = vec![];
+        for (unit, p) in self.terms.iter().filter(|_| power != 0) {
+            terms.push((
+                *unit,
+                p.checked_mul(power).ok_or_else(exponent_out_of_range)?,
+            ));
+        }
+        CompoundUnit { terms }.checked()
+    }
+
+    /// The unit, if its dimension can be represented.
+    fn checked(self) -> Result<CompoundUnit, ConvertError> {
+        match self.checked_dimension() {
+            Some(_) => Ok(self),
+            None => Err(exponent_out_of_range()),
+        }
+    }
+
+    fn push(&mut self, unit: AnyUnit, power: i8) -> Result<(), ConvertError> {
+        match self.terms.iter().position(|(u, _)| *u == unit) {
+            Some(i) => {
+                self.terms[i].1 = self.terms[i]
+                    .1
+                    .checked_add(power)
+                    .ok_or_else(exponent_out_of_range)?;
+                if self.terms[i].1 == 0 {
+                    self.terms.remove(i);
+                }
+            }
+            None if power != 0 => self.terms.push((unit, power)),
+            None => {}
+        }
+        Ok(())
+    }
+}
+
+fn exponent_out_of_range() -> ConvertError {
+    ConvertError::ParseError(format!(
+        "Unit exponents must be between {} and {}",
+        i8::MIN,
+        i8::MAX
+    ))
+}
+
+impl From<AnyUnit> for CompoundUnit {
+    fn from(unit: AnyUnit) -> Self {
+        CompoundUnit {
+            terms: vec![(unit, 1)],
+        }
+    }
+}
+
+impl Mul for CompoundUnit {
+    type Output = Result<CompoundUnit, ConvertError>;
+
+    fn mul(mut self, rhs: CompoundUnit) -> Self::Output {
+        for (unit, power) in rhs.terms {
+            self.push(unit, power)?;
+        }
+        self.checked()
+    }
+}
+
+impl Div for CompoundUnit {
+    type Output = Result<CompoundUnit, ConvertError>;
+
+    fn div(self, rhs: CompoundUnit) -> Self::Output {
+        self * rhs.powi(-1)?
+    }
+}
+
+impl FromStr for CompoundUnit {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // `ft**3` is the same as `ft^3`
+        let normalized = s.replace("**", "^");
+        let mut parser = UnitParser {
+            source: s,
+            chars: normalized.chars().peekable(),
+        };
+        let unit = parser.expression()?;
+        match parser.chars.next() {
+            None => Ok(unit),
+            Some(c) => Err(parser.error(&format!("unexpected '{}'", c))),
+        }
+    }
+}
+
+impl Display for CompoundUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let format_term = |unit: &AnyUnit, power: i16| match power {
+            1 => unit.to_string(),
+            p => format!("{}{}", unit, superscript(p)),
+        };
+        let numerator: Vec<String> = self
+            .terms
+            .iter()
+            .filter(|(_, power)| *power > 0)
+            .map(|(unit, power)| format_term(unit, (*power).into()))
+            .collect();
+        let denominator: Vec<String> = self
+            .terms
+            .iter()
+            .filter(|(_, power)| *power < 0)
+            .map(|(unit, power)| format_term(unit, -i16::from(*power)))
+            .collect();
+
+        match (numerator.is_empty(), denominator.len()) {
+            (true, 0) => write!(f, "1"),
+            (false, 0) => write!(f, "{}", numerator.join("·")),
+            (true, _) => {
+                let terms: Vec<String> = self
+                    .terms
+                    .iter()
+                    .map(|(unit, power)| format_term(unit, (*power).into()))
+                    .collect();
+                write!(f, "{}", terms.join("·"))
+            }
+            (false, 1) => write!(f, "{}/{}", numerator.join("·"), denominator[0]),
+            (false, _) => write!(f, "{}/({})", numerator.join("·"), denominator.join("·")),
+        }
+    }
+}
+
+const SUPERSCRIPT_DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+fn superscript(power: i16) -> String {
+    let digits = power
+        .unsigned_abs()
+        .to_string()
+        .chars()
+        .map(|d| SUPERSCRIPT_DIGITS[d.to_digit(10).unwrap() as usize])
+        .collect::<String>();
+    if power < 0 {
+        format!("⁻{}", digits)
+    } else {
+        digits
+    }
+}
+
+/// Recursive-descent parser for unit expressions:
+///
+/// ```text
+/// expression := factor (('*' | '·' | '/') factor)*
+/// factor     := (unit | '1' | '(' expression ')') power?
+/// power      := ('^' | '**') integer | superscript integer | digits
+/// ```
+///
+/// so `m^2`, `m**2`, `m²` and `m2` all mean square metres, and `s^-1` or `s⁻¹` is per second.
+struct UnitParser<'a> {
+    source: &'a str,
+    chars: Peekable<Chars<'a>>,
+}
+
+impl UnitParser<'_> {
+    fn expression(&mut self) -> Result<CompoundUnit, ConvertError> {
+        let mut unit = self.factor()?;
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some('*' | '·' | '⋅') => {
+                    self.chars.next();
+                    unit = (unit * self.factor()?).map_err(|_| self.power_error())?;
+                }
+                Some('/') => {
+                    self.chars.next();
+                    unit = (unit / self.factor()?).map_err(|_| self.power_error())?;
+                }
+                _ => return Ok(unit),
+            }
+        }
+    }
+
+    fn factor(&mut self) -> Result<CompoundUnit, ConvertError> {
+        self.skip_whitespace();
+        let base = match self.chars.peek() {
+            Some('(') => {
+                self.chars.next();
+                let unit = self.expression()?;
+                self.skip_whitespace();
+                if self.chars.next() != Some(')') {
+                    return Err(self.error("missing ')'"));
+                }
+                unit
+            }
+            Some('1') => {
+                self.chars.next();
+                CompoundUnit::default()
+            }
+            _ => {
+                let name = self.unit_name();
+                if name.is_empty() {
+                    return Err(self.error("expected a unit"));
+                }
+                AnyUnit::from_str(&name)?.into()
+            }
+        };
+        let power = self.power()?;
+        base.powi(power).map_err(|_| self.power_error())
+    }
+
+    fn unit_name(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(&c) = self.chars.peek() {
+            if is_operator(c) || c.is_ascii_digit() || is_superscript(c) {
+                break;
+            }
+            name.push(c);
+            self.chars.next();
+        }
+        name.trim().to_string()
+    }
+
+    fn power(&mut self) -> Result<i8, ConvertError> {
+        self.skip_whitespace();
+        let mut digits = String::new();
+        match self.chars.peek() {
+            Some('^') => {
+                self.chars.next();
+                self.skip_whitespace();
+                if let Some('-' | '−') = self.chars.next_if(|c| matches!(c, '-' | '−' | '+')) {
+                    digits.push('-');
+                }
+                while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+                    digits.push(c);
+                }
+            }
+            Some(c) if c.is_ascii_digit() => {
+                while let Some(c) = self.chars.next_if(char::is_ascii_digit) {
+                    digits.push(c);
+                }
+            }
+            Some(&c) if is_superscript(c) => {
+                while let Some(c) = self.chars.next_if(|c| is_superscript(*c)) {
+                    digits.push(from_superscript(c));
+                }
+            }
+            _ => return Ok(1),
+        }
+        digits.parse().map_err(|_| self.error("invalid exponent"))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn error(&self, reason: &str) -> ConvertError {
+        ConvertError::ParseError(format!("Invalid unit '{}': {}", self.source, reason))
+    }
+
+    /// The error for powers that combine to more than an `i8` holds, like `(m^100)^2`.
+    fn power_error(&self) -> ConvertError {
+        self.error(&format!(
+            "powers must be between {} and {}",
+            i8::MIN,
+            i8::MAX
+        ))
+    }
+}
+
+fn is_operator(c: char) -> bool {
+    matches!(c, '*' | '·' | '⋅' | '/' | '(' | ')' | '^')
+}
+
+fn is_superscript(c: char) -> bool {
+    c == '⁻' || SUPERSCRIPT_DIGITS.contains(&c)
+}
+
+fn from_superscript(c: char) -> char {
+    match SUPERSCRIPT_DIGITS.iter().position(|d| *d == c) {
+        Some(digit) => char::from_digit(digit as u32, 10).unwrap(),
+        None => '-',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        convert::{UnitConverter, dimension::Dimension},
+        error::ConvertError,
+        test_utils::{assert_approx_eq, assert_convert_error},
+    };
+
+    use super::{CompoundConverter, CompoundUnit};
+
+    #[test]
+    fn test_parse_and_display() {
+        let tests = [
+            ("m/s", "m/s"),
+            ("kg·m/s²", "kg·m/s²"),
+            ("kg*m/s^2", "kg·m/s²"),
+            ("lb/in²", "lb/in²"),
+            ("W/(m·K)", "W/(m·K)"),
+            ("m·m", "m²"),
+            ("1/s", "s⁻¹"),
+            ("m/m", "1"),
+        ];
+        for (input, expected) in tests {
+            assert_eq!(CompoundUnit::from_str(input).unwrap().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_compound_dimensions() {
+        let unit = CompoundUnit::from_str("kg·m/s²").unwrap();
+        assert_eq!(
+            unit.dimension(),
+            CompoundUnit::from_str("N").unwrap().dimension()
+        );
+        let unit = CompoundUnit::from_str("W/(m·K)").unwrap();
+        assert_eq!(unit.dimension(), Dimension::new([1, 1, -3, 0, -1, 0, 0]));
+    }
+
+    #[test]
+    fn test_compound_conversions() {
+        let converter = CompoundConverter::new(Dimension::LENGTH / Dimension::TIME);
+        assert_approx_eq(converter.convert(36.0, "km/h", "m/s").unwrap(), 10.0, 1e-9);
+        assert_approx_eq(
+            converter.convert(1.0, "mi/h", "ft/s").unwrap(),
+            1.4667,
+            1e-4,
+        );
+
+        let converter = CompoundConverter::new(Dimension::MASS / Dimension::LENGTH.powi(2));
+        assert_approx_eq(
+            converter.convert(1.0, "lb/in²", "kg/m²").unwrap(),
+            703.07,
+            1e-2,
+        );
+
+        let converter = CompoundConverter::new(Dimension::new([1, 1, -2, 0, 0, 0, 0]));
+        assert_approx_eq(converter.convert(1.0, "kg·m/s²", "N").unwrap(), 1.0, 1e-12);
+
+        let converter = CompoundConverter::new(Dimension::new([1, 1, -3, 0, -1, 0, 0]));
+        let result = converter.convert(1.0, "W/(m·K)", "W/(m·°F)").unwrap();
+        assert_approx_eq(result, 5.0 / 9.0, 1e-12);
+    }
+
+    #[test]
+    fn test_exponent_syntax() {
+        let square_metre = CompoundUnit::from_str("m²").unwrap();
+        for input in ["m^2", "m2", "m**2", "m ^ 2", "m·m"] {
+            assert_eq!(CompoundUnit::from_str(input).unwrap(), square_metre);
+        }
+        let per_second = CompoundUnit::from_str("s⁻¹").unwrap();
+        for input in ["s^-1", "1/s", "s^−1"] {
+            assert_eq!(CompoundUnit::from_str(input).unwrap(), per_second);
+        }
+        assert_eq!(CompoundUnit::from_str("cm³").unwrap().to_string(), "cm³");
+    }
+
+    #[test]
+    fn test_combined_powers_out_of_range() {
+        for input in [
+            "(m**100)**2",
+            "(m¹⁰⁰)²",
+            "m100·m100",
+            "(s⁻¹²⁸)⁻¹",
+            "m^-128/m",
+        ] {
+            assert_eq!(
+                CompoundUnit::from_str(input),
+                Err(ConvertError::ParseError(format!(
+                    "Invalid unit '{}': powers must be between -128 and 127",
+                    input
+                ))),
+            );
+        }
+        assert!(CompoundUnit::from_str("m^200").is_err());
+        assert!(CompoundUnit::from_str("(m**64)**-2").is_ok());
+    }
+
+    #[test]
+    fn test_powers_of_units() {
+        let converter = CompoundConverter::new(Dimension::LENGTH.powi(3));
+        assert_approx_eq(
+            converter.convert(1.0, "m^3", "ft^3").unwrap(),
+            35.3147,
+            1e-4,
+        );
+        assert_approx_eq(
+            converter.convert(1.0, "ft**3", "in3").unwrap(),
+            1728.0,
+            1e-9,
+        );
+        assert_approx_eq(converter.convert(1.0, "m³", "cm³").unwrap(), 1e6, 1e-6);
+
+        let converter = CompoundConverter::new(Dimension::TIME.powi(-1));
+        assert_approx_eq(
+            converter.convert(1.0, "min^-1", "Hz").unwrap(),
+            1.0 / 60.0,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn test_compound_errors() {
+        let converter = CompoundConverter::new(Dimension::LENGTH / Dimension::TIME);
+        assert_convert_error(converter, "m/s", "m/s²", 1.0, |e| {
+            matches!(e, ConvertError::IncompatibleDimensions { .. })
+        });
+        assert!(matches!(
+            CompoundUnit::from_str("m/(s"),
+            Err(ConvertError::ParseError(_))
+        ));
+        assert!(matches!(
+            CompoundUnit::from_str("m^"),
+            Err(ConvertError::ParseError(_))
+        ));
+        assert!(matches!(
+            CompoundUnit::from_str("m/banana"),
+            Err(ConvertError::InvalidUnit(_))
+        ));
+    }
+
+    #[test]
+    fn test_exponent_overflow() {
+        for input in [
+            "(m^100)^2",
+            "m^100·m^100",
+            "ft^100·m^100",
+            "W^50",
+            "1/(s^-128)",
+        ] {
+            assert!(
+                matches!(
+                    CompoundUnit::from_str(input),
+                    Err(ConvertError::ParseError(_))
+                ),
+                "{}",
+                input
+            );
+        }
+        let unit = CompoundUnit::from_str("m^100").unwrap();
+        assert!(matches!(
+            unit.clone() * unit,
+            Err(ConvertError::ParseError(_))
+        ));
+        assert_eq!(
+            CompoundUnit::from_str("m^127").unwrap().dimension().length,
+            127
+        );
+        let unit = CompoundUnit::from_str("s·m^-128").unwrap();
+        assert_eq!(unit.to_string(), "s/m¹²⁸");
+    }
+}
@@ -0,0 +1,623 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use num_rational::BigRational;
+use num_traits::Zero;
+use once_cell::sync::Lazy;
+
+use crate::error::ConvertError;
+
+use super::{
+    AnyUnit,
+    compound::CompoundUnit,
+    derived::DerivedUnit,
+    dimension::Dimension,
+    distance::DistanceUnit,
+    exact::{DECIMAL_DIGITS, decimal, format_decimal, one, pow10, ratio, terminates},
+    mass::MassUnit,
+    temperature::TemperatureUnit,
+    time::TimeUnit,
+};
+
+/// The standard a unit's definition is taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    SiBrochure,
+    NistSp811,
+    NistHandbook44,
+    Iau,
+    Codata2018,
+    Historical,
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::SiBrochure => write!(
+                f,
+                "BIPM, The International System of Units (SI), 9th edition (2019)"
+            ),
+            Source::NistSp811 => write!(f, "NIST Special Publication 811 (2008), Appendix B"),
+            Source::NistHandbook44 => write!(f, "NIST Handbook 44 (2024), Appendix C"),
+            Source::Iau => write!(
+                f,
+                "International Astronomical Union resolutions 2012 B2 and 2015 B2, B3"
+            ),
+            Source::Codata2018 => write!(
+                f,
+                "CODATA 2018 recommended values of the fundamental physical constants"
+            ),
+            Source::Historical => write!(
+                f,
+                "Historical scale definition, no longer maintained by a standards body"
+            ),
+        }
+    }
+}
+
+/// How a unit is defined in terms of the coherent SI units (m, kg, s, K).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    /// The size of one unit in coherent SI units.
+    pub factor: BigRational,
+    /// Where zero on the unit's scale lies in SI units. Only temperature scales have one.
+    pub offset: BigRational,
+    /// Whether the factor is exact. Measured constants and irrational factors are rounded.
+    pub exact: bool,
+    /// The definition as its source states it.
+    pub statement: &'static str,
+    pub source: Source,
+}
+
+impl Definition {
+    fn exact(factor: BigRational, statement: &'static str, source: Source) -> Self {
+        Definition {
+            factor,
+            offset: BigRational::zero(),
+            exact: true,
+            statement,
+            source,
+        }
+    }
+
+    fn rounded(factor: BigRational, statement: &'static str, source: Source) -> Self {
+        Definition {
+            exact: false,
+            ..Definition::exact(factor, statement, source)
+        }
+    }
+
+    fn scale(
+        factor: BigRational,
+        offset: BigRational,
+        statement: &'static str,
+        source: Source,
+    ) -> Self {
+        Definition {
+            offset,
+            ..Definition::exact(factor, statement, source)
+        }
+    }
+}
+
+/// The definition of a unit. Prefixes are ignored, and a temperature difference shares the
+/// definition of its scale.
+pub fn definition(unit: &AnyUnit) -> &'static Definition {
+    let key = match unit {
+        AnyUnit::Distance(u) => AnyUnit::Distance(u.unit.into()),
+        AnyUnit::Mass(u) => AnyUnit::Mass(u.unit.into()),
+        AnyUnit::Temperature(u) => AnyUnit::Temperature(u.unit.into()),
+        AnyUnit::TemperatureDelta(u) => AnyUnit::Temperature(u.0.into()),
+        AnyUnit::Time(u) => AnyUnit::Time(u.unit.into()),
+        AnyUnit::Derived(u) => AnyUnit::Derived(u.unit.into()),
+    };
+    DEFINITIONS
+        .get(&key)
+        .unwrap_or_else(|| panic!("no definition for '{}'", key))
+}
+
+/// A description of a unit for `unitconvert info`: its dimension, definition and source.
+pub fn describe(unit_str: &str) -> Result<String, ConvertError> {
+    let unit = AnyUnit::from_str(unit_str)?;
+    let definition = definition(&unit);
+    let si_unit = coherent_unit(unit.dimension());
+
+    let mut lines = vec![
+        format!("{}", unit),
+        format!("  Dimension:  {}", unit.dimension()),
+        format!("  Definition: {}", definition.statement),
+    ];
+    let prefix = match unit {
+        AnyUnit::Distance(u) => u.prefix,
+        AnyUnit::Mass(u) => u.prefix,
+        AnyUnit::Temperature(u) => u.prefix,
+        AnyUnit::Time(u) => u.prefix,
+        AnyUnit::Derived(u) => u.prefix,
+        AnyUnit::TemperatureDelta(_) => None,
+    };
+    if let Some(prefix) = prefix {
+        lines.push(format!(
+            "  Prefix:     {} ({}) = 10^{}",
+            prefix.symbol(),
+            prefix.name(),
+            prefix.exponent()
+        ));
+    }
+    let amount = |value: &BigRational| {
+        if definition.exact {
+            exact_amount(value, &si_unit)
+        } else {
+            format!("{} {}", format_decimal(value, DECIMAL_DIGITS), si_unit)
+        }
+    };
+    lines.push(format!(
+        "  {}1 {} = {}",
+        if definition.exact {
+            "Exactly:    "
+        } else {
+            "Rounded:    "
+        },
+        unit,
+        amount(&unit.exact_factor())
+    ));
+    if !definition.offset.is_zero() && matches!(unit, AnyUnit::Temperature(_)) {
+        lines.push(format!("  Zero point: {}", amount(&definition.offset)));
+    }
+    lines.push(format!("  Source:     {}", definition.source));
+    Ok(lines.join("\n"))
+}
+
+/// An exact amount of a unit. A decimal that would not end is written as a fraction, with
+/// its approximate value alongside: `1200/3937 m (≈ 0.304800609601219 m)`.
+fn exact_amount(value: &BigRational, unit: &CompoundUnit) -> String {
+    if terminates(value) {
+        return format!("{} {}", format_decimal(value, DECIMAL_DIGITS), unit);
+    }
+    format!(
+        "{} {} (≈ {} {})",
+        value,
+        unit,
+        format_decimal(value, f64::DIGITS as usize),
+        unit
+    )
+}
+
+/// The coherent SI unit of a dimension, e.g. `kg·m/s²` for force. Only the base units
+/// that some unit here is built from are covered.
+fn coherent_unit(dimension: Dimension) -> CompoundUnit {
+    [
+        ("kg", dimension.mass),
+        ("m", dimension.length),
+        ("s", dimension.time),
+        ("K", dimension.temperature),
+    ]
+    .into_iter()
+    .filter(|(_, power)| *power != 0)
+    .try_fold(CompoundUnit::default(), |unit, (symbol, power)| {
+        let base = CompoundUnit::from_str(symbol).expect("SI base units parse");
+        unit * base.powi(power)?
+    })
+    .expect("the exponents of a dimension fit a unit")
+}
+
+static DEFINITIONS: Lazy<HashMap<AnyUnit, Definition>> = Lazy::new(|| {
+    use Source::*;
+
+    let pound = decimal("0.45359237");
+    let grain = &pound / ratio(7000, 1);
+    let foot = decimal("0.3048");
+    let ice_point = decimal("273.15");
+
+    let distance = |unit: DistanceUnit| AnyUnit::Distance(unit.into());
+    let mass = |unit: MassUnit| AnyUnit::Mass(unit.into());
+    let time = |unit: TimeUnit| AnyUnit::Time(unit.into());
+    let temperature = |unit: TemperatureUnit| AnyUnit::Temperature(unit.into());
+    let derived = |unit: DerivedUnit| AnyUnit::Derived(unit.into());
+
+    HashMap::from([
+        // Length
+        (
+            distance(DistanceUnit::Meter),
+            Definition::exact(
+                one(),
+                "SI base unit, fixed by c = 299 792 458 m/s",
+                SiBrochure,
+            ),
+        ),
+        (
+            distance(DistanceUnit::Kilometer),
+            Definition::exact(pow10(3), "1 km = 1000 m", SiBrochure),
+        ),
+        (
+            distance(DistanceUnit::Centimeter),
+            Definition::exact(pow10(-2), "1 cm = 0.01 m", SiBrochure),
+        ),
+        (
+            distance(DistanceUnit::Millimeter),
+            Definition::exact(pow10(-3), "1 mm = 0.001 m", SiBrochure),
+        ),
+        (
+            distance(DistanceUnit::Micrometer),
+            Definition::exact(pow10(-6), "1 µm = 10^-6 m", SiBrochure),
+        ),
+        (
+            distance(DistanceUnit::Nanometer),
+            Definition::exact(pow10(-9), "1 nm = 10^-9 m", SiBrochure),
+        ),
+        (
+            distance(DistanceUnit::Angstrom),
+            Definition::exact(pow10(-10), "1 Å = 10^-10 m", NistSp811),
+        ),
+        (
+            distance(DistanceUnit::Inch),
+            Definition::exact(decimal("0.0254"), "1 in = 2.54 cm", NistSp811),
+        ),
+        (
+            distance(DistanceUnit::Thou),
+            Definition::exact(decimal("0.0000254"), "1 thou = 0.001 in", NistSp811),
+        ),
+        (
+            distance(DistanceUnit::Hand),
+            Definition::exact(decimal("0.1016"), "1 hh = 4 in", NistHandbook44),
+        ),
+        (
+            distance(DistanceUnit::Foot),
+            Definition::exact(foot.clone(), "1 ft = 12 in = 0.3048 m", NistSp811),
+        ),
+        (
+            distance(DistanceUnit::Yard),
+            Definition::exact(decimal("0.9144"), "1 yd = 3 ft = 0.9144 m", NistSp811),
+        ),
+        (
+            distance(DistanceUnit::Fathom),
+            Definition::exact(&foot * ratio(6, 1), "1 ftm = 6 ft", NistHandbook44),
+        ),
+        (
+            distance(DistanceUnit::Rod),
+            Definition::exact(&foot * ratio(33, 2), "1 rd = 16.5 ft", NistHandbook44),
+        ),
+        (
+            distance(DistanceUnit::Chain),
+            Definition::exact(&foot * ratio(66, 1), "1 ch = 4 rd = 66 ft", NistHandbook44),
+        ),
+        (
+            distance(DistanceUnit::Furlong),
+            Definition::exact(
+                &foot * ratio(660, 1),
+                "1 fur = 10 ch = 660 ft",
+                NistHandbook44,
+            ),
+        ),
+        (
+            distance(DistanceUnit::Mile),
+            Definition::exact(
+                decimal("1609.344"),
+                "1 mi = 5280 ft = 1609.344 m",
+                NistSp811,
+            ),
+        ),
+        (
+            distance(DistanceUnit::NauticalMile),
+            Definition::exact(ratio(1852, 1), "1 nmi = 1852 m", NistSp811),
+        ),
+        (
+            distance(DistanceUnit::UsSurveyFoot),
+            Definition::exact(ratio(1200, 3937), "1 ftUS = 1200/3937 m", NistSp811),
+        ),
+        (
+            distance(DistanceUnit::UsSurveyMile),
+            Definition::exact(ratio(6_336_000, 3937), "1 miUS = 5280 ftUS", NistSp811),
+        ),
+        (
+            distance(DistanceUnit::LightSecond),
+            Definition::exact(ratio(299_792_458, 1), "1 ls = c × 1 s", SiBrochure),
+        ),
+        (
+            distance(DistanceUnit::AstronomicalUnit),
+            Definition::exact(ratio(149_597_870_700, 1), "1 au = 149 597 870 700 m", Iau),
+        ),
+        (
+            distance(DistanceUnit::LightYear),
+            Definition::exact(
+                ratio(9_460_730_472_580_800, 1),
+                "1 ly = c × 1 Julian year (365.25 d)",
+                Iau,
+            ),
+        ),
+        (
+            distance(DistanceUnit::Parsec),
+            Definition::rounded(decimal("30856775814913673"), "1 pc = 648000/π au", Iau),
+        ),
+        (
+            distance(DistanceUnit::EarthRadius),
+            Definition::exact(
+                ratio(6_378_100, 1),
+                "Nominal equatorial Earth radius, 6 378.1 km",
+                Iau,
+            ),
+        ),
+        (
+            distance(DistanceUnit::SolarRadius),
+            Definition::exact(
+                ratio(695_700_000, 1),
+                "Nominal solar radius, 695 700 km",
+                Iau,
+            ),
+        ),
+        // Mass
+        (
+            mass(MassUnit::Kilogram),
+            Definition::exact(
+                one(),
+                "SI base unit, fixed by h = 6.626 070 15 × 10^-34 J·s",
+                SiBrochure,
+            ),
+        ),
+        (
+            mass(MassUnit::Gram),
+            Definition::exact(pow10(-3), "1 g = 0.001 kg", SiBrochure),
+        ),
+        (
+            mass(MassUnit::Milligram),
+            Definition::exact(pow10(-6), "1 mg = 10^-6 kg", SiBrochure),
+        ),
+        (
+            mass(MassUnit::Microgram),
+            Definition::exact(pow10(-9), "1 µg = 10^-9 kg", SiBrochure),
+        ),
+        (
+            mass(MassUnit::Tonne),
+            Definition::exact(pow10(3), "1 t = 1000 kg", SiBrochure),
+        ),
+        (
+            mass(MassUnit::Pound),
+            Definition::exact(pound.clone(), "1 lb = 0.453 592 37 kg", NistSp811),
+        ),
+        (
+            mass(MassUnit::Ounce),
+            Definition::exact(&pound / ratio(16, 1), "1 oz = 1/16 lb", NistSp811),
+        ),
+        (
+            mass(MassUnit::Stone),
+            Definition::exact(&pound * ratio(14, 1), "1 st = 14 lb", NistHandbook44),
+        ),
+        (
+            mass(MassUnit::ShortTon),
+            Definition::exact(&pound * ratio(2000, 1), "1 ton = 2000 lb", NistSp811),
+        ),
+        (
+            mass(MassUnit::LongTon),
+            Definition::exact(&pound * ratio(2240, 1), "1 LT = 2240 lb", NistSp811),
+        ),
+        (
+            mass(MassUnit::Grain),
+            Definition::exact(grain.clone(), "1 gr = 1/7000 lb", NistSp811),
+        ),
+        (
+            mass(MassUnit::Pennyweight),
+            Definition::exact(&grain * ratio(24, 1), "1 dwt = 24 gr", NistSp811),
+        ),
+        (
+            mass(MassUnit::TroyOunce),
+            Definition::exact(&grain * ratio(480, 1), "1 oz t = 480 gr", NistSp811),
+        ),
+        (
+            mass(MassUnit::TroyPound),
+            Definition::exact(&grain * ratio(5760, 1), "1 lb t = 12 oz t", NistSp811),
+        ),
+        (
+            mass(MassUnit::Carat),
+            Definition::exact(ratio(1, 5000), "1 ct = 200 mg", NistSp811),
+        ),
+        (
+            mass(MassUnit::Slug),
+            Definition::exact(
+                &pound * decimal("9.80665") / &foot,
+                "1 slug = 1 lbf·s²/ft",
+                NistSp811,
+            ),
+        ),
+        (
+            mass(MassUnit::Dalton),
+            Definition::rounded(
+                decimal("1.66053906660e-27"),
+                "1 Da = 1/12 of the mass of a carbon-12 atom",
+                Codata2018,
+            ),
+        ),
+        // Time
+        (
+            time(TimeUnit::Second),
+            Definition::exact(
+                one(),
+                "SI base unit, fixed by the caesium frequency ΔνCs",
+                SiBrochure,
+            ),
+        ),
+        (
+            time(TimeUnit::Minute),
+            Definition::exact(ratio(60, 1), "1 min = 60 s", SiBrochure),
+        ),
+        (
+            time(TimeUnit::Hour),
+            Definition::exact(ratio(3600, 1), "1 h = 60 min", SiBrochure),
+        ),
+        (
+            time(TimeUnit::Day),
+            Definition::exact(ratio(86_400, 1), "1 d = 24 h", SiBrochure),
+        ),
+        (
+            time(TimeUnit::Week),
+            Definition::exact(ratio(604_800, 1), "1 wk = 7 d", NistHandbook44),
+        ),
+        (
+            time(TimeUnit::Year),
+            Definition::exact(ratio(31_557_600, 1), "1 yr = 1 Julian year = 365.25 d", Iau),
+        ),
+        // Temperature
+        (
+            temperature(TemperatureUnit::Kelvin),
+            Definition::exact(
+                one(),
+                "SI base unit, fixed by k = 1.380 649 × 10^-23 J/K",
+                SiBrochure,
+            ),
+        ),
+        (
+            temperature(TemperatureUnit::Celsius),
+            Definition::scale(one(), ice_point.clone(), "t/°C = T/K − 273.15", SiBrochure),
+        ),
+        (
+            temperature(TemperatureUnit::Fahrenheit),
+            Definition::scale(
+                ratio(5, 9),
+                decimal("459.67") * ratio(5, 9),
+                "t/°F = 1.8 t/°C + 32",
+                NistSp811,
+            ),
+        ),
+        (
+            temperature(TemperatureUnit::Rankine),
+            Definition::exact(ratio(5, 9), "T/°R = 1.8 T/K", NistSp811),
+        ),
+        (
+            temperature(TemperatureUnit::Reaumur),
+            Definition::scale(
+                ratio(5, 4),
+                ice_point.clone(),
+                "t/°Ré = 0.8 t/°C",
+                Historical,
+            ),
+        ),
+        (
+            // The Delisle scale runs backwards from the boiling point
+            temperature(TemperatureUnit::Delisle),
+            Definition::scale(
+                ratio(-2, 3),
+                decimal("373.15"),
+                "t/°De = 1.5 (100 − t/°C)",
+                Historical,
+            ),
+        ),
+        (
+            temperature(TemperatureUnit::Newton),
+            Definition::scale(
+                ratio(100, 33),
+                ice_point.clone(),
+                "t/°N = 0.33 t/°C",
+                Historical,
+            ),
+        ),
+        (
+            // Water freezes at 7.5 °Rø
+            temperature(TemperatureUnit::Romer),
+            Definition::scale(
+                ratio(40, 21),
+                ice_point - ratio(100, 7),
+                "t/°Rø = 21/40 t/°C + 7.5",
+                Historical,
+            ),
+        ),
+        // Derived
+        (
+            derived(DerivedUnit::Newton),
+            Definition::exact(one(), "1 N = 1 kg·m/s²", SiBrochure),
+        ),
+        (
+            derived(DerivedUnit::Joule),
+            Definition::exact(one(), "1 J = 1 N·m", SiBrochure),
+        ),
+        (
+            derived(DerivedUnit::Watt),
+            Definition::exact(one(), "1 W = 1 J/s", SiBrochure),
+        ),
+        (
+            derived(DerivedUnit::Pascal),
+            Definition::exact(one(), "1 Pa = 1 N/m²", SiBrochure),
+        ),
+        (
+            derived(DerivedUnit::Hertz),
+            Definition::exact(one(), "1 Hz = 1 s⁻¹", SiBrochure),
+        ),
+    ])
+});
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use strum::IntoEnumIterator;
+
+    use crate::convert::{
+        AnyUnit,
+        derived::DerivedUnit,
+        distance::DistanceUnit,
+        exact::{decimal, ratio},
+        mass::MassUnit,
+        temperature::TemperatureUnit,
+        time::TimeUnit,
+    };
+
+    use super::{DEFINITIONS, Source, definition, describe};
+
+    #[test]
+    fn test_every_unit_is_defined() {
+        let count = DistanceUnit::iter().count()
+            + MassUnit::iter().count()
+            + TimeUnit::iter().count()
+            + TemperatureUnit::iter().count()
+            + DerivedUnit::iter().count();
+        assert_eq!(DEFINITIONS.len(), count);
+    }
+
+    #[test]
+    fn test_exact_definitions() {
+        let factor = |unit: &str| definition(&AnyUnit::from_str(unit).unwrap()).factor.clone();
+        assert_eq!(factor("mi"), decimal("1609.344"));
+        assert_eq!(factor("lb"), decimal("0.45359237"));
+        assert_eq!(factor("oz"), decimal("0.028349523125"));
+        assert_eq!(factor("gr"), decimal("0.00006479891"));
+        assert_eq!(factor("fur"), decimal("201.168"));
+        assert_eq!(factor("dF"), ratio(5, 9));
+
+        let mile = definition(&AnyUnit::from_str("mi").unwrap());
+        assert!(mile.exact);
+        assert_eq!(mile.source, Source::NistSp811);
+        assert!(!definition(&AnyUnit::from_str("pc").unwrap()).exact);
+    }
+
+    #[test]
+    fn test_describe() {
+        let info = describe("mi").unwrap();
+        assert!(info.contains("1 mi = 5280 ft = 1609.344 m"), "{}", info);
+        assert!(info.contains("NIST Special Publication 811"), "{}", info);
+
+        let info = describe("kN").unwrap();
+        assert!(info.contains("Prefix:     k (kilo) = 10^3"), "{}", info);
+        assert!(info.contains("1 kN = 1000 kg·m/s²"), "{}", info);
+
+        let info = describe("°F").unwrap();
+        assert!(
+            info.contains("Exactly:    1 °F = 5/9 K (≈ 0.555555555555556 K)"),
+            "{}",
+            info
+        );
+        assert!(
+            info.contains("Zero point: 45967/180 K (≈ 255.372"),
+            "{}",
+            info
+        );
+
+        let info = describe("ftUS").unwrap();
+        assert!(
+            info.contains("Exactly:    1 ftUS = 1200/3937 m (≈ 0.304800609601219 m)"),
+            "{}",
+            info
+        );
+        assert!(
+            describe("ft")
+                .unwrap()
+                .contains("Exactly:    1 ft = 0.3048 m")
+        );
+        assert!(describe("banana").is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use num_rational::BigRational;
+use once_cell::sync::Lazy;
+use strum_macros::EnumIter;
+
+use crate::error::ConvertError;
+
+use super::{AnyUnit, definitions::definition, dimension::Dimension, prefix::Prefixable};
+
+/// Named SI derived units. They have no converter of their own; they take part in
+/// conversions as terms of a [`CompoundUnit`](super::compound::CompoundUnit).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum DerivedUnit {
+    Newton,
+    Joule,
+    Watt,
+    Pascal,
+    Hertz,
+}
+
+impl DerivedUnit {
+    pub fn dimension(&self) -> Dimension {
+        match self {
+            DerivedUnit::Newton => Dimension::new([1, 1, -2, 0, 0, 0, 0]),
+            DerivedUnit::Joule => Dimension::new([2, 1, -2, 0, 0, 0, 0]),
+            DerivedUnit::Watt => Dimension::new([2, 1, -3, 0, 0, 0, 0]),
+            DerivedUnit::Pascal => Dimension::new([-1, 1, -2, 0, 0, 0, 0]),
+            DerivedUnit::Hertz => Dimension::new([0, 0, -1, 0, 0, 0, 0]),
+        }
+    }
+
+    pub fn accepted_string() -> Vec<&'static str> {
+        DERIVED_UNIT_STRINGS.keys().copied().collect()
+    }
+}
+
+impl Prefixable for DerivedUnit {
+    fn is_prefixable(&self) -> bool {
+        true
+    }
+
+    fn exact_factor(&self) -> BigRational {
+        definition(&AnyUnit::Derived((*self).into())).factor.clone()
+    }
+}
+
+impl FromStr for DerivedUnit {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DERIVED_UNIT_STRINGS
+            .get(s.to_lowercase().as_str())
+            .copied()
+            .ok_or(ConvertError::InvalidUnit(s.to_string()))
+    }
+}
+
+impl Display for DerivedUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DerivedUnit::Newton => write!(f, "N"),
+            DerivedUnit::Joule => write!(f, "J"),
+            DerivedUnit::Watt => write!(f, "W"),
+            DerivedUnit::Pascal => write!(f, "Pa"),
+            DerivedUnit::Hertz => write!(f, "Hz"),
+        }
+    }
+}
+
+static DERIVED_UNIT_STRINGS: Lazy<HashMap<&'static str, DerivedUnit>> = Lazy::new(|| {
+    use DerivedUnit::*;
+    let mut map = HashMap::new();
+    map.insert("n", Newton);
+    map.insert("newton", Newton);
+    map.insert("newtons", Newton);
+    map.insert("j", Joule);
+    map.insert("joule", Joule);
+    map.insert("joules", Joule);
+    map.insert("w", Watt);
+    map.insert("watt", Watt);
+    map.insert("watts", Watt);
+    map.insert("pa", Pascal);
+    map.insert("pascal", Pascal);
+    map.insert("pascals", Pascal);
+    map.insert("hz", Hertz);
+    map.insert("hertz", Hertz);
+    map
+});
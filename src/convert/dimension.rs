@@ -0,0 +1,213 @@
+use std::{
+    fmt::Display,
+    ops::{Div, Mul},
+};
+
+/// The physical dimension of a unit, as exponents over the seven SI base dimensions.
+///
+/// Two units can only be converted into one another when their dimensions are equal, so a
+/// metre (`L`) converts to a foot but not to a kilogram (`M`), and a newton (`M·L·T^-2`)
+/// converts to any other unit of force.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Dimension {
+    pub length: i8,
+    pub mass: i8,
+    pub time: i8,
+    pub current: i8,
+    pub temperature: i8,
+    pub amount: i8,
+    pub luminous_intensity: i8,
+}
+
+impl Dimension {
+    pub const DIMENSIONLESS: Dimension = Dimension::new([0, 0, 0, 0, 0, 0, 0]);
+    pub const LENGTH: Dimension = Dimension::new([1, 0, 0, 0, 0, 0, 0]);
+    pub const MASS: Dimension = Dimension::new([0, 1, 0, 0, 0, 0, 0]);
+    pub const TIME: Dimension = Dimension::new([0, 0, 1, 0, 0, 0, 0]);
+    pub const CURRENT: Dimension = Dimension::new([0, 0, 0, 1, 0, 0, 0]);
+    pub const TEMPERATURE: Dimension = Dimension::new([0, 0, 0, 0, 1, 0, 0]);
+    pub const AMOUNT: Dimension = Dimension::new([0, 0, 0, 0, 0, 1, 0]);
+    pub const LUMINOUS_INTENSITY: Dimension = Dimension::new([0, 0, 0, 0, 0, 0, 1]);
+
+    /// Builds a dimension from its exponents, in the SI order
+    /// length, mass, time, current, temperature, amount, luminous intensity.
+    pub const fn new(exponents: [i8; 7]) -> Self {
+        let [
+            length,
+            mass,
+            time,
+            current,
+            temperature,
+            amount,
+            luminous_intensity,
+        ] = exponents;
+        Dimension {
+            length,
+            mass,
+            time,
+            current,
+            temperature,
+            amount,
+            luminous_intensity,
+        }
+    }
+
+    pub fn exponents(&self) -> [i8; 7] {
+        [
+            self.length,
+            self.mass,
+            self.time,
+            self.current,
+            self.temperature,
+            self.amount,
+            self.luminous_intensity,
+        ]
+    }
+
+    /// Raises the dimension to a power.
+    ///
+    /// Panics if an exponent leaves the range of `i8`; use [`Self::checked_powi`] for powers
+    /// that come from user input.
+    pub fn powi(self, power: i8) -> Self {
+        self.checked_powi(power)
+            .expect("dimension exponent out of range")
+    }
+
+    /// Raises the dimension to a power, or `None` if an exponent leaves the range of `i8`.
+    pub fn checked_powi(self, power: i8) -> Option<Self> {
+        let mut exponents = self.exponents();
+        for exponent in &mut exponents {
+            *exponent = exponent.checked_mul(power)?;
+        }
+        Some(Dimension::new(exponents))
+    }
+
+    /// The dimension of a product, or `None` if an exponent leaves the range of `i8`.
+    pub fn checked_mul(self, rhs: Dimension) -> Option<Self> {
+        let mut exponents = self.exponents();
+        for (exponent, other) in exponents.iter_mut().zip(rhs.exponents()) {
+            *exponent = exponent.checked_add(other)?;
+        }
+        Some(Dimension::new(exponents))
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Dimension::DIMENSIONLESS
+    }
+
+    /// The common name of the quantity, if the dimension is a well-known one.
+    pub fn name(&self) -> Option<&'static str> {
+        KNOWN_DIMENSIONS
+            .iter()
+            .find(|(exponents, _)| *exponents == self.exponents())
+            .map(|(_, name)| *name)
+    }
+
+    /// The dimension written in terms of the base dimension symbols, e.g. `L·T^-1`.
+    pub fn formula(&self) -> String {
+        if self.is_dimensionless() {
+            return "1".to_string();
+        }
+        SYMBOLS
+            .iter()
+            .zip(self.exponents())
+            .filter(|(_, exponent)| *exponent != 0)
+            .map(|(symbol, exponent)| match exponent {
+                1 => symbol.to_string(),
+                e => format!("{}^{}", symbol, e),
+            })
+            .collect::<Vec<_>>()
+            .join("·")
+    }
+}
+
+const SYMBOLS: [&str; 7] = ["L", "M", "T", "I", "Θ", "N", "J"];
+
+const KNOWN_DIMENSIONS: [([i8; 7], &str); 17] = [
+    ([0, 0, 0, 0, 0, 0, 0], "dimensionless"),
+    ([1, 0, 0, 0, 0, 0, 0], "length"),
+    ([0, 1, 0, 0, 0, 0, 0], "mass"),
+    ([0, 0, 1, 0, 0, 0, 0], "time"),
+    ([0, 0, 0, 1, 0, 0, 0], "electric current"),
+    ([0, 0, 0, 0, 1, 0, 0], "temperature"),
+    ([0, 0, 0, 0, 0, 1, 0], "amount of substance"),
+    ([0, 0, 0, 0, 0, 0, 1], "luminous intensity"),
+    ([2, 0, 0, 0, 0, 0, 0], "area"),
+    ([3, 0, 0, 0, 0, 0, 0], "volume"),
+    ([1, 0, -1, 0, 0, 0, 0], "velocity"),
+    ([1, 0, -2, 0, 0, 0, 0], "acceleration"),
+    ([0, 0, -1, 0, 0, 0, 0], "frequency"),
+    ([1, 1, -2, 0, 0, 0, 0], "force"),
+    ([-1, 1, -2, 0, 0, 0, 0], "pressure"),
+    ([2, 1, -2, 0, 0, 0, 0], "energy"),
+    ([2, 1, -3, 0, 0, 0, 0], "power"),
+];
+
+impl Mul for Dimension {
+    type Output = Dimension;
+
+    fn mul(self, rhs: Dimension) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("dimension exponent out of range")
+    }
+}
+
+impl Div for Dimension {
+    type Output = Dimension;
+
+    fn div(self, rhs: Dimension) -> Self::Output {
+        self * rhs.powi(-1)
+    }
+}
+
+impl Display for Dimension {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.name() {
+            Some(name) => write!(f, "{}", name),
+            None => write!(f, "{}", self.formula()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Dimension;
+
+    #[test]
+    fn test_dimension_algebra() {
+        let velocity = Dimension::LENGTH / Dimension::TIME;
+        assert_eq!(velocity.name(), Some("velocity"));
+        let force = Dimension::MASS * velocity / Dimension::TIME;
+        assert_eq!(force.to_string(), "force");
+        assert_eq!(Dimension::LENGTH.powi(3).to_string(), "volume");
+        assert!((Dimension::LENGTH / Dimension::LENGTH).is_dimensionless());
+    }
+
+    #[test]
+    fn test_dimension_formula() {
+        let conductivity =
+            Dimension::MASS * Dimension::LENGTH / Dimension::TIME.powi(3) / Dimension::TEMPERATURE;
+        assert_eq!(conductivity.formula(), "L·M·T^-3·Θ^-1");
+        assert_eq!(conductivity.to_string(), "L·M·T^-3·Θ^-1");
+        assert_eq!(Dimension::DIMENSIONLESS.formula(), "1");
+    }
+
+    #[test]
+    fn test_checked_algebra() {
+        let long = Dimension::LENGTH.checked_powi(100).unwrap();
+        assert_eq!(long.length, 100);
+        assert_eq!(long.checked_mul(long), None);
+        assert_eq!(long.checked_powi(2), None);
+        assert_eq!(
+            Dimension::LENGTH.checked_powi(i8::MIN).unwrap().length,
+            i8::MIN
+        );
+        assert_eq!(
+            Dimension::LENGTH
+                .checked_powi(i8::MIN)
+                .unwrap()
+                .checked_powi(-1),
+            None
+        );
+    }
+}
@@ -1,5 +1,13 @@
-use crate::convert::UnitConverter;
+use crate::convert::{
+    AnyUnit, UnitConverter,
+    definitions::definition,
+    dimension::Dimension,
+    exact::scale,
+    prefix::{Prefixable, Prefixed},
+    typed::static_units,
+};
 use crate::error::ConvertError;
+use num_rational::BigRational;
 use once_cell::sync::Lazy;
 use std::{collections::HashMap, fmt, str::FromStr};
 use strum::IntoEnumIterator;
@@ -8,61 +16,86 @@ use strum_macros::EnumIter;
 pub struct DistanceConverter;
 
 #[derive(Debug, Clone, Copy)]
-struct Meter(f64);
+pub struct Meter;
 #[derive(Debug, Clone, Copy)]
-struct Kilometer(f64);
+pub struct Kilometer;
 #[derive(Debug, Clone, Copy)]
-struct Foot(f64);
+pub struct Foot;
 #[derive(Debug, Clone, Copy)]
-struct Mile(f64);
+pub struct Mile;
 #[derive(Debug, Clone, Copy)]
-struct Inch(f64);
+pub struct Inch;
 #[derive(Debug, Clone, Copy)]
-struct Centimeter(f64);
+pub struct Centimeter;
 #[derive(Debug, Clone, Copy)]
-struct Millimeter(f64);
+pub struct Millimeter;
+#[derive(Debug, Clone, Copy)]
+pub struct Yard;
+#[derive(Debug, Clone, Copy)]
+pub struct NauticalMile;
+#[derive(Debug, Clone, Copy)]
+pub struct Micrometer;
+#[derive(Debug, Clone, Copy)]
+pub struct Nanometer;
+#[derive(Debug, Clone, Copy)]
+pub struct Angstrom;
+#[derive(Debug, Clone, Copy)]
+pub struct Fathom;
+#[derive(Debug, Clone, Copy)]
+pub struct Furlong;
+#[derive(Debug, Clone, Copy)]
+pub struct Chain;
+#[derive(Debug, Clone, Copy)]
+pub struct Rod;
+#[derive(Debug, Clone, Copy)]
+pub struct Hand;
+#[derive(Debug, Clone, Copy)]
+pub struct Thou;
+#[derive(Debug, Clone, Copy)]
+pub struct UsSurveyFoot;
+#[derive(Debug, Clone, Copy)]
+pub struct UsSurveyMile;
+#[derive(Debug, Clone, Copy)]
+pub struct AstronomicalUnit;
+#[derive(Debug, Clone, Copy)]
+pub struct LightYear;
+#[derive(Debug, Clone, Copy)]
+pub struct Parsec;
+#[derive(Debug, Clone, Copy)]
+pub struct LightSecond;
+#[derive(Debug, Clone, Copy)]
+pub struct EarthRadius;
+#[derive(Debug, Clone, Copy)]
+pub struct SolarRadius;
+
+static_units!(
+    DistanceUnit, Meter:
+    Meter, Kilometer, Foot, Mile, Inch, Centimeter, Millimeter, Yard, NauticalMile, Micrometer,
+    Nanometer, Angstrom, Fathom, Furlong, Chain, Rod, Hand, Thou, UsSurveyFoot, UsSurveyMile,
+    AstronomicalUnit, LightYear, Parsec, LightSecond, EarthRadius, SolarRadius,
+);
 
 impl DistanceConverter {
     pub fn get_unit_string(&self, unit_str: &str) -> String {
-        if let Ok(unit) = DistanceUnit::from_str(unit_str) {
+        if let Ok(unit) = Prefixed::<DistanceUnit>::from_str(unit_str) {
             unit.to_string()
         } else {
             unit_str.to_string()
         }
     }
-
-    fn to_meter(value: f64, unit: &DistanceUnit) -> Meter {
-        match unit {
-            DistanceUnit::Meter => Meter(value),
-            DistanceUnit::Kilometer => Kilometer(value).into(),
-            DistanceUnit::Foot => Foot(value).into(),
-            DistanceUnit::Mile => Mile(value).into(),
-            DistanceUnit::Inch => Inch(value).into(),
-            DistanceUnit::Centimeter => Centimeter(value).into(),
-            DistanceUnit::Millimeter => Millimeter(value).into(),
-        }
-    }
-
-    fn from_meter(meters: Meter, unit: &DistanceUnit) -> f64 {
-        match unit {
-            DistanceUnit::Meter => meters.0,
-            DistanceUnit::Kilometer => Kilometer::from(meters).0,
-            DistanceUnit::Foot => Foot::from(meters).0,
-            DistanceUnit::Mile => Mile::from(meters).0,
-            DistanceUnit::Inch => Inch::from(meters).0,
-            DistanceUnit::Centimeter => Centimeter::from(meters).0,
-            DistanceUnit::Millimeter => Millimeter::from(meters).0,
-        }
-    }
 }
 
 impl UnitConverter for DistanceConverter {
     fn convert(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError> {
-        let from_unit = DistanceUnit::from_str(from)?;
-        let to_unit = DistanceUnit::from_str(to)?;
+        let from_unit = Prefixed::<DistanceUnit>::from_str(from)?;
+        let to_unit = Prefixed::<DistanceUnit>::from_str(to)?;
+
+        let factor = from_unit.exact_factor() / to_unit.exact_factor();
+        Ok(scale(value, &factor))
+    }
 
-        let meters = Self::to_meter(value, &from_unit);
-        Ok(Self::from_meter(meters, &to_unit))
+    fn dimension(&self) -> Dimension {
+        Dimension::LENGTH
     }
 
     fn supported_units(&self) -> Vec<String> {
@@ -72,80 +105,6 @@ impl UnitConverter for DistanceConverter {
     }
 }
 
-// Convert to Meter
-impl From<Kilometer> for Meter {
-    fn from(value: Kilometer) -> Self {
-        Meter(value.0 * 1000.0)
-    }
-}
-
-impl From<Foot> for Meter {
-    fn from(value: Foot) -> Self {
-        Meter(value.0 * 0.3048)
-    }
-}
-
-impl From<Mile> for Meter {
-    fn from(value: Mile) -> Self {
-        Meter(value.0 * 1609.34)
-    }
-}
-
-impl From<Inch> for Meter {
-    fn from(value: Inch) -> Self {
-        Meter(value.0 * 0.0254)
-    }
-}
-
-impl From<Centimeter> for Meter {
-    fn from(value: Centimeter) -> Self {
-        Meter(value.0 * 0.01)
-    }
-}
-
-impl From<Millimeter> for Meter {
-    fn from(value: Millimeter) -> Self {
-        Meter(value.0 * 0.001)
-    }
-}
-
-// Convert from Meter
-impl From<Meter> for Kilometer {
-    fn from(value: Meter) -> Self {
-        Kilometer(value.0 / 1000.0)
-    }
-}
-
-impl From<Meter> for Foot {
-    fn from(value: Meter) -> Self {
-        Foot(value.0 / 0.3048)
-    }
-}
-
-impl From<Meter> for Mile {
-    fn from(value: Meter) -> Self {
-        Mile(value.0 / 1609.34)
-    }
-}
-
-impl From<Meter> for Inch {
-    fn from(value: Meter) -> Self {
-        Inch(value.0 / 0.0254)
-    }
-}
-
-impl From<Meter> for Centimeter {
-    fn from(value: Meter) -> Self {
-        Centimeter(value.0 / 0.01)
-    }
-}
-
-impl From<Meter> for Millimeter {
-    fn from(value: Meter) -> Self {
-        Millimeter(value.0 / 0.001)
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
 pub enum DistanceUnit {
     Meter,
@@ -155,14 +114,52 @@ pub enum DistanceUnit {
     Inch,
     Centimeter,
     Millimeter,
+    Yard,
+    NauticalMile,
+    Micrometer,
+    Nanometer,
+    Angstrom,
+    Fathom,
+    Furlong,
+    Chain,
+    Rod,
+    Hand,
+    Thou,
+    UsSurveyFoot,
+    UsSurveyMile,
+    AstronomicalUnit,
+    LightYear,
+    Parsec,
+    LightSecond,
+    EarthRadius,
+    SolarRadius,
 }
 
 impl DistanceUnit {
+    pub fn dimension(&self) -> Dimension {
+        Dimension::LENGTH
+    }
+
     pub fn accepted_string() -> Vec<&'static str> {
         DISTANCE_UNIT_STRINGS.keys().copied().collect()
     }
 }
 
+impl Prefixable for DistanceUnit {
+    fn is_prefixable(&self) -> bool {
+        matches!(
+            self,
+            DistanceUnit::Meter | DistanceUnit::Parsec | DistanceUnit::LightYear
+        )
+    }
+
+    fn exact_factor(&self) -> BigRational {
+        definition(&AnyUnit::Distance((*self).into()))
+            .factor
+            .clone()
+    }
+}
+
 impl FromStr for DistanceUnit {
     type Err = ConvertError;
 
@@ -184,6 +181,25 @@ impl fmt::Display for DistanceUnit {
             DistanceUnit::Inch => write!(f, "in"),
             DistanceUnit::Centimeter => write!(f, "cm"),
             DistanceUnit::Millimeter => write!(f, "mm"),
+            DistanceUnit::Yard => write!(f, "yd"),
+            DistanceUnit::NauticalMile => write!(f, "nmi"),
+            DistanceUnit::Micrometer => write!(f, "µm"),
+            DistanceUnit::Nanometer => write!(f, "nm"),
+            DistanceUnit::Angstrom => write!(f, "Å"),
+            DistanceUnit::Fathom => write!(f, "ftm"),
+            DistanceUnit::Furlong => write!(f, "fur"),
+            DistanceUnit::Chain => write!(f, "ch"),
+            DistanceUnit::Rod => write!(f, "rd"),
+            DistanceUnit::Hand => write!(f, "hh"),
+            DistanceUnit::Thou => write!(f, "thou"),
+            DistanceUnit::UsSurveyFoot => write!(f, "ftUS"),
+            DistanceUnit::UsSurveyMile => write!(f, "miUS"),
+            DistanceUnit::AstronomicalUnit => write!(f, "au"),
+            DistanceUnit::LightYear => write!(f, "ly"),
+            DistanceUnit::Parsec => write!(f, "pc"),
+            DistanceUnit::LightSecond => write!(f, "ls"),
+            DistanceUnit::EarthRadius => write!(f, "R⊕"),
+            DistanceUnit::SolarRadius => write!(f, "R☉"),
         }
     }
 }
@@ -220,6 +236,87 @@ static DISTANCE_UNIT_STRINGS: Lazy<HashMap<&'static str, DistanceUnit>> = Lazy::
     map.insert("millimeters", Millimeter);
     map.insert("millimetre", Millimeter);
     map.insert("millimetres", Millimeter);
+    map.insert("yd", Yard);
+    map.insert("yard", Yard);
+    map.insert("yards", Yard);
+    map.insert("nmi", NauticalMile);
+    map.insert("nautical mile", NauticalMile);
+    map.insert("nautical miles", NauticalMile);
+    map.insert("µm", Micrometer);
+    map.insert("μm", Micrometer);
+    map.insert("um", Micrometer);
+    map.insert("micrometer", Micrometer);
+    map.insert("micrometers", Micrometer);
+    map.insert("micrometre", Micrometer);
+    map.insert("micrometres", Micrometer);
+    map.insert("micron", Micrometer);
+    map.insert("microns", Micrometer);
+    map.insert("nm", Nanometer);
+    map.insert("nanometer", Nanometer);
+    map.insert("nanometers", Nanometer);
+    map.insert("nanometre", Nanometer);
+    map.insert("nanometres", Nanometer);
+    map.insert("å", Angstrom);
+    map.insert("angstrom", Angstrom);
+    map.insert("angstroms", Angstrom);
+    map.insert("ångström", Angstrom);
+    map.insert("ångströms", Angstrom);
+    map.insert("ftm", Fathom);
+    map.insert("fathom", Fathom);
+    map.insert("fathoms", Fathom);
+    map.insert("fur", Furlong);
+    map.insert("furlong", Furlong);
+    map.insert("furlongs", Furlong);
+    map.insert("ch", Chain);
+    map.insert("chain", Chain);
+    map.insert("chains", Chain);
+    map.insert("rd", Rod);
+    map.insert("rod", Rod);
+    map.insert("rods", Rod);
+    map.insert("pole", Rod);
+    map.insert("poles", Rod);
+    map.insert("perch", Rod);
+    map.insert("perches", Rod);
+    map.insert("hh", Hand);
+    map.insert("hand", Hand);
+    map.insert("hands", Hand);
+    map.insert("thou", Thou);
+    map.insert("mil", Thou);
+    map.insert("mils", Thou);
+    map.insert("ftus", UsSurveyFoot);
+    map.insert("us survey foot", UsSurveyFoot);
+    map.insert("us survey feet", UsSurveyFoot);
+    map.insert("survey foot", UsSurveyFoot);
+    map.insert("survey feet", UsSurveyFoot);
+    map.insert("mius", UsSurveyMile);
+    map.insert("us survey mile", UsSurveyMile);
+    map.insert("us survey miles", UsSurveyMile);
+    map.insert("survey mile", UsSurveyMile);
+    map.insert("survey miles", UsSurveyMile);
+    map.insert("au", AstronomicalUnit);
+    map.insert("astronomical unit", AstronomicalUnit);
+    map.insert("astronomical units", AstronomicalUnit);
+    map.insert("ly", LightYear);
+    map.insert("light-year", LightYear);
+    map.insert("light-years", LightYear);
+    map.insert("lightyear", LightYear);
+    map.insert("lightyears", LightYear);
+    map.insert("pc", Parsec);
+    map.insert("parsec", Parsec);
+    map.insert("parsecs", Parsec);
+    map.insert("ls", LightSecond);
+    map.insert("light-second", LightSecond);
+    map.insert("light-seconds", LightSecond);
+    map.insert("lightsecond", LightSecond);
+    map.insert("lightseconds", LightSecond);
+    map.insert("r⊕", EarthRadius);
+    map.insert("rearth", EarthRadius);
+    map.insert("earth radius", EarthRadius);
+    map.insert("earth radii", EarthRadius);
+    map.insert("r☉", SolarRadius);
+    map.insert("rsun", SolarRadius);
+    map.insert("solar radius", SolarRadius);
+    map.insert("solar radii", SolarRadius);
     map
 });
 
@@ -249,6 +346,50 @@ mod tests {
         assert_approx_eq(result, 1.0, 1e-5);
     }
 
+    #[test]
+    fn test_astronomical_units() {
+        let converter = DistanceConverter;
+        let result = converter.convert(1.0, "pc", "ly").unwrap();
+        assert_approx_eq(result, 3.26156, 1e-5);
+        let result = converter.convert(1.0, "au", "km").unwrap();
+        assert_approx_eq(result, 149_597_870.7, 1e-3);
+        let result = converter.convert(1.0, "R☉", "R⊕").unwrap();
+        assert_approx_eq(result, 109.0763, 1e-4);
+    }
+
+    #[test]
+    fn test_everyday_and_nautical_units() {
+        let tests = [
+            (1.0, "yd", "ft", 3.0),
+            (1.0, "nmi", "m", 1852.0),
+            (1.0, "µm", "nm", 1000.0),
+            (1.0, "nm", "Å", 10.0),
+            (1.0, "fathom", "ft", 6.0),
+            (1.0, "furlong", "chains", 10.0),
+            (1.0, "chain", "rods", 4.0),
+            (1.0, "hand", "in", 4.0),
+            (1000.0, "mil", "in", 1.0),
+            (1.0, "survey mile", "survey feet", 5280.0),
+        ];
+        let converter = DistanceConverter;
+        for (value, from, to, expected) in tests {
+            let result = converter.convert(value, from, to).unwrap();
+            assert_approx_eq(result, expected, 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_prefixed_units() {
+        let converter = DistanceConverter;
+        assert_approx_eq(converter.convert(1.0, "Mm", "km").unwrap(), 1000.0, 1e-9);
+        assert_approx_eq(converter.convert(1.0, "mm", "µm").unwrap(), 1000.0, 1e-9);
+        assert_approx_eq(converter.convert(1.0, "Mpc", "pc").unwrap(), 1e6, 1e-6);
+        assert_convert_error(DistanceConverter, "kft", "m", 1.0, |e| {
+            matches!(e, ConvertError::InvalidUnit(_))
+        });
+        assert_eq!(converter.get_unit_string("Gm"), "Gm");
+    }
+
     #[test]
     fn test_invalid_unit() {
         assert_convert_error(DistanceConverter, "banana", "ft", 1.0, |e| {
@@ -0,0 +1,227 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::error::ConvertError;
+
+/// The number of significant digits kept when a decimal result does not terminate.
+pub const DECIMAL_DIGITS: usize = 30;
+
+/// The largest power of ten a written number may carry, e.g. `1e1000`. Far beyond what any
+/// unit needs, but small enough that the exact value stays cheap to work with.
+pub const MAX_DECIMAL_EXPONENT: i32 = 1000;
+
+/// Parses a decimal number such as `1609.344`, `-.5` or `6.957e8` into an exact rational,
+/// without passing through `f64`.
+pub fn parse_decimal(s: &str) -> Result<BigRational, ConvertError> {
+    let invalid = || ConvertError::ParseError("Invalid number".to_string());
+    let s = s.trim();
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(i) => (&s[..i], s[i + 1..].parse::<i64>().map_err(|_| invalid())?),
+        None => (s, 0),
+    };
+    let exponent = i32::try_from(exponent)
+        .ok()
+        .filter(|exponent| exponent.abs() <= MAX_DECIMAL_EXPONENT)
+        .ok_or_else(|| {
+            ConvertError::ParseError(format!(
+                "Exponents must be between -{0} and {0}",
+                MAX_DECIMAL_EXPONENT
+            ))
+        })?;
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let unsigned = integer.strip_prefix(['+', '-']).unwrap_or(integer);
+
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if unsigned.len() + fraction.len() == 0 || !is_digits(unsigned) || !is_digits(fraction) {
+        return Err(invalid());
+    }
+
+    let digits: BigInt = format!("{}{}", integer, fraction)
+        .parse()
+        .map_err(|_| invalid())?;
+    Ok(BigRational::from_integer(digits) * pow10(exponent - fraction.len() as i32))
+}
+
+/// Writes an exact value as a plain decimal, rounded half away from zero to at most
+/// `significant_digits` significant digits, e.g. `1/8` is `0.125` and `1/3` to five digits is
+/// `0.33333`.
+pub fn format_decimal(value: &BigRational, significant_digits: usize) -> String {
+    if value.is_zero() || significant_digits == 0 {
+        return "0".to_string();
+    }
+    let magnitude = value.abs();
+    let ten = BigRational::from_integer(10.into());
+    let mut exponent = leading_exponent(value);
+
+    let digits_count = significant_digits as i32;
+    let mut rounded = (&magnitude * pow10(digits_count - 1 - exponent)).round();
+    if rounded >= pow10(digits_count) {
+        rounded /= ten;
+        exponent += 1;
+    }
+    let digits = rounded.to_integer().to_string();
+
+    let (integer, fraction) = if exponent >= digits_count - 1 {
+        let zeros = "0".repeat((exponent - digits_count + 1) as usize);
+        (format!("{}{}", digits, zeros), String::new())
+    } else if exponent >= 0 {
+        let (integer, fraction) = digits.split_at(exponent as usize + 1);
+        (integer.to_string(), fraction.to_string())
+    } else {
+        let zeros = "0".repeat((-exponent - 1) as usize);
+        ("0".to_string(), format!("{}{}", zeros, digits))
+    };
+
+    let sign = if value.is_negative() { "-" } else { "" };
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{}{}", sign, integer)
+    } else {
+        format!("{}{}.{}", sign, integer, fraction)
+    }
+}
+
+/// Whether a value can be written as a decimal that ends, like `1/8` but not `1/3`.
+pub fn terminates(value: &BigRational) -> bool {
+    let mut denominator = value.denom().clone();
+    for factor in [2, 5] {
+        let factor = BigInt::from(factor);
+        while (&denominator % &factor).is_zero() {
+            denominator /= &factor;
+        }
+    }
+    denominator.is_one()
+}
+
+/// The power of ten of the leading digit of a non-zero value, e.g. `2` for `254`.
+pub(crate) fn leading_exponent(value: &BigRational) -> i32 {
+    let magnitude = value.abs();
+    let mut exponent: i32 = 0;
+    while magnitude >= pow10(exponent + 1) {
+        exponent += 1;
+    }
+    while magnitude < pow10(exponent) {
+        exponent -= 1;
+    }
+    exponent
+}
+
+/// The exact value of a decimal literal, for unit definitions written in the source.
+pub(crate) fn decimal(literal: &str) -> BigRational {
+    parse_decimal(literal).expect("unit definitions are valid decimals")
+}
+
+pub(crate) fn ratio(numerator: i64, denominator: i64) -> BigRational {
+    BigRational::new(numerator.into(), denominator.into())
+}
+
+pub(crate) fn one() -> BigRational {
+    BigRational::one()
+}
+
+/// Ten to the given power, exactly.
+pub fn pow10(exponent: i32) -> BigRational {
+    BigRational::from_integer(10.into()).pow(exponent)
+}
+
+/// Parses a number for float mode. Numbers that overflow an `f64`, like `1e400`, are refused
+/// rather than becoming infinite.
+pub fn parse_float(s: &str) -> Result<f64, ConvertError> {
+    let s = s.trim();
+    let value: f64 = s
+        .parse()
+        .map_err(|_| ConvertError::ParseError("Invalid number".to_string()))?;
+    check_finite(value).map_err(|_| out_of_float_range(s))
+}
+
+/// The value, if it is finite.
+pub fn check_finite(value: f64) -> Result<f64, ConvertError> {
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(out_of_float_range(&value.to_string()))
+    }
+}
+
+fn out_of_float_range(number: &str) -> ConvertError {
+    ConvertError::ParseError(format!("{} is out of range for float mode", number))
+}
+
+/// The exact value of a float, e.g. `0.1` is `3602879701896397/36028797018963968`.
+pub fn from_f64(value: f64) -> Result<BigRational, ConvertError> {
+    BigRational::from_float(value).ok_or(ConvertError::ParseError(format!(
+        "Cannot convert {} exactly",
+        value
+    )))
+}
+
+/// Multiplies a float by an exact factor, rounding only once at the end.
+pub(crate) fn scale(value: f64, factor: &BigRational) -> f64 {
+    match BigRational::from_float(value) {
+        Some(exact) => to_f64(&(exact * factor)),
+        None => value * to_f64(factor),
+    }
+}
+
+/// The nearest float to an exact value.
+pub fn to_f64(value: &BigRational) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ConvertError;
+
+    use super::{decimal, format_decimal, parse_decimal, parse_float, pow10, ratio, terminates};
+
+    #[test]
+    fn test_parse_decimal() {
+        assert_eq!(parse_decimal("0.0254"), Ok(ratio(127, 5000)));
+        assert_eq!(parse_decimal("-.5"), Ok(ratio(-1, 2)));
+        assert_eq!(parse_decimal("6.957e8"), Ok(ratio(695_700_000, 1)));
+        assert_eq!(parse_decimal("25E-3"), Ok(ratio(1, 40)));
+        for invalid in ["", ".", "1.2.3", "abc", "1e", "--1", "1.-2", "1_000"] {
+            assert_eq!(
+                parse_decimal(invalid),
+                Err(ConvertError::ParseError("Invalid number".to_string())),
+                "parsing '{}'",
+                invalid
+            );
+        }
+        assert_eq!(parse_decimal("1e1000"), Ok(pow10(1000)));
+        assert_eq!(parse_float("1e300"), Ok(1e300));
+        assert!(parse_float("1e400").is_err());
+        assert!(parse_float("-inf").is_err());
+        for huge in ["1e9999999", "1e-1001", "1e99999999999"] {
+            assert!(
+                matches!(parse_decimal(huge), Err(ConvertError::ParseError(message)) if message.contains("Exponents")),
+                "parsing '{}'",
+                huge
+            );
+        }
+    }
+
+    #[test]
+    fn test_terminates() {
+        assert!(terminates(&ratio(1, 8)));
+        assert!(terminates(&ratio(127, 5000)));
+        assert!(terminates(&ratio(-7, 1)));
+        assert!(!terminates(&ratio(1200, 3937)));
+        assert!(!terminates(&ratio(5, 9)));
+    }
+
+    #[test]
+    fn test_format_decimal() {
+        assert_eq!(format_decimal(&ratio(1, 8), 30), "0.125");
+        assert_eq!(format_decimal(&ratio(-1, 3), 5), "-0.33333");
+        assert_eq!(format_decimal(&ratio(2, 3), 5), "0.66667");
+        assert_eq!(format_decimal(&ratio(99_999, 1), 3), "100000");
+        assert_eq!(format_decimal(&ratio(0, 1), 30), "0");
+        assert_eq!(format_decimal(&decimal("0.3"), 30), "0.3");
+        assert_eq!(
+            format_decimal(&decimal("1.6605e-27"), 30),
+            "0.0000000000000000000000000016605"
+        );
+    }
+}
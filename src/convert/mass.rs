@@ -1,68 +1,101 @@
 use std::{collections::HashMap, fmt::Display, str::FromStr};
 
+use num_rational::BigRational;
 use once_cell::sync::Lazy;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use crate::error::ConvertError;
 
-use super::UnitConverter;
+use super::{
+    AnyUnit, UnitConverter,
+    definitions::definition,
+    dimension::Dimension,
+    exact::scale,
+    prefix::{Prefixable, Prefixed},
+    typed::static_units,
+};
 
 pub struct MassConverter;
 
 #[derive(Debug, Clone, Copy)]
-struct Gram(f64);
+pub struct Gram;
 
 #[derive(Debug, Clone, Copy)]
-struct Kilogram(f64);
+pub struct Kilogram;
 
 #[derive(Debug, Clone, Copy)]
-struct Ounce(f64);
+pub struct Ounce;
 
 #[derive(Debug, Clone, Copy)]
-struct Pound(f64);
+pub struct Pound;
 
 #[derive(Debug, Clone, Copy)]
-struct Stone(f64);
+pub struct Stone;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Milligram;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Microgram;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Tonne;
+
+#[derive(Debug, Clone, Copy)]
+pub struct ShortTon;
+
+#[derive(Debug, Clone, Copy)]
+pub struct LongTon;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Grain;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Carat;
+
+#[derive(Debug, Clone, Copy)]
+pub struct TroyOunce;
+
+#[derive(Debug, Clone, Copy)]
+pub struct TroyPound;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Pennyweight;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Slug;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Dalton;
+
+static_units!(
+    MassUnit, Gram:
+    Gram, Kilogram, Ounce, Pound, Stone, Milligram, Microgram, Tonne, ShortTon, LongTon, Grain,
+    Carat, TroyOunce, TroyPound, Pennyweight, Slug, Dalton,
+);
 
 impl MassConverter {
     pub fn get_unit_string(&self, unit_str: &str) -> String {
-        if let Ok(unit) = MassUnit::from_str(unit_str) {
+        if let Ok(unit) = Prefixed::<MassUnit>::from_str(unit_str) {
             unit.to_string()
         } else {
             unit_str.to_string()
         }
     }
-
-    fn to_grams(value: f64, unit: &MassUnit) -> Gram {
-        // Use grams as the base unit
-        match unit {
-            MassUnit::Gram => Gram(value),
-            MassUnit::Kilogram => Kilogram(value).into(),
-            MassUnit::Ounce => Ounce(value).into(),
-            MassUnit::Pound => Pound(value).into(),
-            MassUnit::Stone => Stone(value).into(),
-        }
-    }
-
-    fn from_grams(grams: Gram, unit: &MassUnit) -> f64 {
-        match unit {
-            MassUnit::Gram => grams.0,
-            MassUnit::Kilogram => Kilogram::from(grams).0,
-            MassUnit::Ounce => Ounce::from(grams).0,
-            MassUnit::Pound => Pound::from(grams).0,
-            MassUnit::Stone => Stone::from(grams).0,
-        }
-    }
 }
 
 impl UnitConverter for MassConverter {
     fn convert(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError> {
-        let from_unit = MassUnit::from_str(from)?;
-        let to_unit = MassUnit::from_str(to)?;
+        let from_unit = Prefixed::<MassUnit>::from_str(from)?;
+        let to_unit = Prefixed::<MassUnit>::from_str(to)?;
 
-        let grams = Self::to_grams(value, &from_unit);
-        Ok(Self::from_grams(grams, &to_unit))
+        let factor = from_unit.exact_factor() / to_unit.exact_factor();
+        Ok(scale(value, &factor))
+    }
+
+    fn dimension(&self) -> Dimension {
+        Dimension::MASS
     }
 
     fn supported_units(&self) -> Vec<String> {
@@ -72,56 +105,6 @@ impl UnitConverter for MassConverter {
     }
 }
 
-// Convert to Gram
-impl From<Kilogram> for Gram {
-    fn from(value: Kilogram) -> Self {
-        Gram(value.0 * 1000.0)
-    }
-}
-
-impl From<Pound> for Gram {
-    fn from(value: Pound) -> Self {
-        Gram(value.0 * 453.59291)
-    }
-}
-
-impl From<Ounce> for Gram {
-    fn from(value: Ounce) -> Self {
-        Gram(value.0 * 28.34949)
-    }
-}
-
-impl From<Stone> for Gram {
-    fn from(value: Stone) -> Self {
-        Gram(value.0 * 6350.29497)
-    }
-}
-
-// Convert from Gram
-impl From<Gram> for Kilogram {
-    fn from(value: Gram) -> Self {
-        Kilogram(value.0 / 1000.0)
-    }
-}
-
-impl From<Gram> for Pound {
-    fn from(value: Gram) -> Self {
-        Pound(value.0 / 453.59291)
-    }
-}
-
-impl From<Gram> for Ounce {
-    fn from(value: Gram) -> Self {
-        Ounce(value.0 / 28.34949)
-    }
-}
-
-impl From<Gram> for Stone {
-    fn from(value: Gram) -> Self {
-        Stone(value.0 / 6350.29497)
-    }
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
 pub enum MassUnit {
     Kilogram,
@@ -129,14 +112,40 @@ pub enum MassUnit {
     Stone,
     Ounce,
     Gram,
+    Milligram,
+    Microgram,
+    Tonne,
+    ShortTon,
+    LongTon,
+    Grain,
+    Carat,
+    TroyOunce,
+    TroyPound,
+    Pennyweight,
+    Slug,
+    Dalton,
 }
 
 impl MassUnit {
+    pub fn dimension(&self) -> Dimension {
+        Dimension::MASS
+    }
+
     pub fn accepted_string() -> Vec<&'static str> {
         MASS_UNIT_STRINGS.keys().copied().collect()
     }
 }
 
+impl Prefixable for MassUnit {
+    fn is_prefixable(&self) -> bool {
+        matches!(self, MassUnit::Gram | MassUnit::Tonne | MassUnit::Dalton)
+    }
+
+    fn exact_factor(&self) -> BigRational {
+        definition(&AnyUnit::Mass((*self).into())).factor.clone()
+    }
+}
+
 impl FromStr for MassUnit {
     type Err = ConvertError;
 
@@ -156,6 +165,18 @@ impl Display for MassUnit {
             MassUnit::Stone => write!(f, "st"),
             MassUnit::Ounce => write!(f, "oz"),
             MassUnit::Gram => write!(f, "g"),
+            MassUnit::Milligram => write!(f, "mg"),
+            MassUnit::Microgram => write!(f, "µg"),
+            MassUnit::Tonne => write!(f, "t"),
+            MassUnit::ShortTon => write!(f, "tn"),
+            MassUnit::LongTon => write!(f, "LT"),
+            MassUnit::Grain => write!(f, "gr"),
+            MassUnit::Carat => write!(f, "ct"),
+            MassUnit::TroyOunce => write!(f, "oz t"),
+            MassUnit::TroyPound => write!(f, "lb t"),
+            MassUnit::Pennyweight => write!(f, "dwt"),
+            MassUnit::Slug => write!(f, "slug"),
+            MassUnit::Dalton => write!(f, "Da"),
         }
     }
 }
@@ -178,6 +199,57 @@ static MASS_UNIT_STRINGS: Lazy<HashMap<&'static str, MassUnit>> = Lazy::new(|| {
     map.insert("g", Gram);
     map.insert("gram", Gram);
     map.insert("grams", Gram);
+    map.insert("mg", Milligram);
+    map.insert("milligram", Milligram);
+    map.insert("milligrams", Milligram);
+    map.insert("milligramme", Milligram);
+    map.insert("milligrammes", Milligram);
+    map.insert("µg", Microgram);
+    map.insert("μg", Microgram);
+    map.insert("ug", Microgram);
+    map.insert("mcg", Microgram);
+    map.insert("microgram", Microgram);
+    map.insert("micrograms", Microgram);
+    map.insert("microgramme", Microgram);
+    map.insert("microgrammes", Microgram);
+    map.insert("t", Tonne);
+    map.insert("tonne", Tonne);
+    map.insert("tonnes", Tonne);
+    map.insert("metric ton", Tonne);
+    map.insert("metric tons", Tonne);
+    map.insert("tn", ShortTon);
+    map.insert("ton", ShortTon);
+    map.insert("tons", ShortTon);
+    map.insert("short ton", ShortTon);
+    map.insert("short tons", ShortTon);
+    map.insert("lt", LongTon);
+    map.insert("long ton", LongTon);
+    map.insert("long tons", LongTon);
+    map.insert("imperial ton", LongTon);
+    map.insert("imperial tons", LongTon);
+    map.insert("gr", Grain);
+    map.insert("grain", Grain);
+    map.insert("grains", Grain);
+    map.insert("ct", Carat);
+    map.insert("carat", Carat);
+    map.insert("carats", Carat);
+    map.insert("oz t", TroyOunce);
+    map.insert("ozt", TroyOunce);
+    map.insert("troy ounce", TroyOunce);
+    map.insert("troy ounces", TroyOunce);
+    map.insert("lb t", TroyPound);
+    map.insert("lbt", TroyPound);
+    map.insert("troy pound", TroyPound);
+    map.insert("troy pounds", TroyPound);
+    map.insert("dwt", Pennyweight);
+    map.insert("pennyweight", Pennyweight);
+    map.insert("pennyweights", Pennyweight);
+    map.insert("slug", Slug);
+    map.insert("slugs", Slug);
+    map.insert("da", Dalton);
+    map.insert("u", Dalton);
+    map.insert("dalton", Dalton);
+    map.insert("daltons", Dalton);
     map
 });
 
@@ -193,4 +265,26 @@ mod tests {
         let result = converter.convert(2.5, "kg", "lb").unwrap();
         assert_approx_eq(5.51155, result, 1e-4);
     }
+
+    #[test]
+    fn test_extended_units() {
+        let tests = [
+            (1.0, "g", "mg", 1000.0),
+            (1.0, "mg", "µg", 1000.0),
+            (1.0, "tonne", "kg", 1000.0),
+            (1.0, "short ton", "kg", 907.18474),
+            (1.0, "long ton", "kg", 1016.0469088),
+            (1.0, "ct", "mg", 200.0),
+            (1.0, "troy pound", "ozt", 12.0),
+            (1.0, "ozt", "dwt", 20.0),
+            (1.0, "dwt", "grains", 24.0),
+            (1.0, "slug", "kg", 14.593902937206),
+            (1.0, "Da", "g", 1.66053906660e-24),
+        ];
+        let converter = MassConverter;
+        for (value, from, to, expected) in tests {
+            let result = converter.convert(value, from, to).unwrap();
+            assert_approx_eq(result, expected, 1e-9);
+        }
+    }
 }
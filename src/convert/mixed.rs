@@ -0,0 +1,195 @@
+use std::str::FromStr;
+
+use num_rational::BigRational;
+use num_traits::{Signed, Zero};
+
+use crate::{error::ConvertError, format::Formatter};
+
+use super::AnyUnit;
+
+/// A target made of several units of one dimension, such as `ft+in`, `st+lb` or `h:min:s`.
+/// Results are broken into whole numbers of each unit, with the remainder in the smallest.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MixedUnit {
+    /// The units, largest first.
+    units: Vec<AnyUnit>,
+    /// Whether the result is written like a clock, `1:30:15`, rather than `1 h 30 min 15 s`.
+    clock: bool,
+}
+
+impl MixedUnit {
+    /// Reads a conversion target, giving `None` when it is a single unit.
+    pub fn parse_target(to: &str) -> Result<Option<MixedUnit>, ConvertError> {
+        match MixedUnit::from_str(to) {
+            Ok(mixed) => Ok(Some(mixed)),
+            Err(ConvertError::InvalidUnit(_)) if !to.contains(['+', ':']) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// The unit the remainder is given in, which is the one to convert into before splitting.
+    pub fn smallest(&self) -> &AnyUnit {
+        self.units.last().expect("a mixed unit has several units")
+    }
+
+    /// Breaks the size of a value measured in the smallest unit into one amount per unit. All
+    /// but the last are whole numbers.
+    pub fn split(&self, value: &BigRational) -> Vec<BigRational> {
+        let smallest = self.smallest().exact_factor();
+        let mut remaining = value.abs() * &smallest;
+        let mut amounts = vec![];
+        for unit in &self.units[..self.units.len() - 1] {
+            let factor = unit.exact_factor();
+            let whole = (&remaining / &factor).floor();
+            remaining -= &whole * factor;
+            amounts.push(whole);
+        }
+        amounts.push(remaining / smallest);
+        amounts
+    }
+
+    /// Writes a value measured in the smallest unit, e.g. `71` for `ft+in` is `5 ft 11 in`. The
+    /// formatter's rounding applies to the value as a whole, before it is split, so that a
+    /// remainder never rounds up to a whole larger unit (`5 ft 12 in`).
+    pub fn format(&self, value: &BigRational, formatter: &Formatter) -> String {
+        let rounded = match formatter.rounding {
+            Some(rounding) => rounding.apply(value),
+            None => value.clone(),
+        };
+        let plain = Formatter {
+            rounding: None,
+            ..*formatter
+        };
+        let sign = if rounded.is_negative() { "-" } else { "" };
+        let amounts = self.split(&rounded);
+
+        if self.clock {
+            let fields: Vec<String> = amounts
+                .iter()
+                .enumerate()
+                .map(|(i, amount)| {
+                    let field = plain.format_exact(amount);
+                    let is_single_digit = *amount < BigRational::from_integer(10.into());
+                    if i > 0 && is_single_digit {
+                        format!("0{}", field)
+                    } else {
+                        field
+                    }
+                })
+                .collect();
+            return format!("{}{}", sign, fields.join(":"));
+        }
+
+        // Leading units with nothing in them are left out, so 11 in is not `0 ft 11 in`
+        let last = amounts.len() - 1;
+        let fields: Vec<String> = amounts
+            .iter()
+            .zip(&self.units)
+            .enumerate()
+            .skip_while(|(i, (amount, _))| *i < last && amount.is_zero())
+            .map(|(_, (amount, unit))| format!("{} {}", plain.format_exact(amount), unit))
+            .collect();
+        format!("{}{}", sign, fields.join(" "))
+    }
+}
+
+impl FromStr for MixedUnit {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let clock = s.contains(':');
+        let parts: Vec<&str> = s.split([':', '+']).map(str::trim).collect();
+        if parts.len() < 2 {
+            return Err(ConvertError::InvalidUnit(s.to_string()));
+        }
+        let mut units = parts
+            .iter()
+            .map(|part| AnyUnit::from_str(part))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let first = units[0];
+        if let Some(other) = units.iter().find(|u| u.dimension() != first.dimension()) {
+            return Err(ConvertError::IncompatibleDimensions {
+                from: first.to_string(),
+                from_dimension: first.dimension(),
+                to: other.to_string(),
+                to_dimension: other.dimension(),
+            });
+        }
+        // Absolute temperatures are points on a scale, so they cannot be split into parts
+        if let AnyUnit::Temperature(_) = first {
+            return Err(ConvertError::ParseError(format!(
+                "Cannot split a temperature into '{}'",
+                s
+            )));
+        }
+
+        units.sort_by_key(|unit| std::cmp::Reverse(unit.exact_factor()));
+        Ok(MixedUnit { units, clock })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        convert::exact::{decimal, ratio},
+        error::ConvertError,
+        format::{Formatter, Rounding},
+    };
+
+    use super::MixedUnit;
+
+    #[test]
+    fn test_parse_mixed_unit() {
+        let mixed = MixedUnit::from_str("in+ft").unwrap();
+        assert_eq!(mixed.smallest().to_string(), "in");
+        assert_eq!(MixedUnit::parse_target("ft"), Ok(None));
+        assert!(MixedUnit::parse_target("ft+in").unwrap().is_some());
+        assert!(matches!(
+            MixedUnit::from_str("ft+kg"),
+            Err(ConvertError::IncompatibleDimensions { .. })
+        ));
+        assert!(matches!(
+            MixedUnit::from_str("c+f"),
+            Err(ConvertError::ParseError(_))
+        ));
+        assert!(matches!(
+            MixedUnit::parse_target("ft+banana"),
+            Err(ConvertError::InvalidUnit(_))
+        ));
+    }
+
+    #[test]
+    fn test_split() {
+        let mixed = MixedUnit::from_str("st+lb").unwrap();
+        assert_eq!(mixed.split(&ratio(185, 1)), vec![ratio(13, 1), ratio(3, 1)]);
+        let mixed = MixedUnit::from_str("h:min:s").unwrap();
+        assert_eq!(
+            mixed.split(&decimal("5415.5")),
+            vec![ratio(1, 1), ratio(30, 1), decimal("15.5")]
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let formatter = Formatter::default();
+        let mixed = MixedUnit::from_str("ft+in").unwrap();
+        assert_eq!(mixed.format(&ratio(71, 1), &formatter), "5 ft 11 in");
+        assert_eq!(mixed.format(&ratio(11, 1), &formatter), "11 in");
+        assert_eq!(mixed.format(&ratio(-71, 1), &formatter), "-5 ft 11 in");
+        assert_eq!(mixed.format(&ratio(60, 1), &formatter), "5 ft 0 in");
+
+        // 71.99 in rounds to 72 in before splitting, never to 5 ft 12 in
+        let rounded = Formatter {
+            rounding: Some(Rounding::SignificantFigures(2)),
+            ..Formatter::default()
+        };
+        assert_eq!(mixed.format(&decimal("71.99"), &rounded), "6 ft 0 in");
+
+        let clock = MixedUnit::from_str("h:min:s").unwrap();
+        assert_eq!(clock.format(&ratio(5415, 1), &formatter), "1:30:15");
+        assert_eq!(clock.format(&decimal("303.5"), &formatter), "0:05:03.5");
+    }
+}
@@ -1,33 +1,215 @@
-use std::str::FromStr;
+use std::{collections::HashSet, fmt::Display, str::FromStr};
 
+use num_rational::BigRational;
+
+use compound::{CompoundConverter, CompoundUnit};
+use derived::DerivedUnit;
+use dimension::Dimension;
 use distance::{DistanceConverter, DistanceUnit};
 use mass::{MassConverter, MassUnit};
-use temperature::{TemperatureConverter, TemperatureUnit};
+use prefix::{Prefix, Prefixed};
+use strum::IntoEnumIterator;
+use temperature::{TemperatureConverter, TemperatureDelta, TemperatureUnit};
+use time::{TimeConverter, TimeUnit};
 
 use crate::error::ConvertError;
 
+pub mod best;
+pub mod compound;
+pub mod definitions;
+pub mod derived;
+pub mod dimension;
 pub mod distance;
+pub mod exact;
 pub mod mass;
+pub mod mixed;
+pub mod prefix;
 pub mod temperature;
+pub mod time;
+pub mod typed;
 
 pub trait UnitConverter {
     fn convert(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError>;
 
+    fn dimension(&self) -> Dimension;
+
     fn supported_units(&self) -> Vec<String>;
 }
 
+/// Picks the converter for a pair of units, based on their physical dimensions.
+///
+/// Single units use the converter for their category; anything built from several units
+/// (`m/s`, `kg·m/s²`) or without a category of its own (`N`) uses a [`CompoundConverter`].
 pub fn get_converter(from: &str, to: &str) -> Result<AnyConverter, ConvertError> {
-    if DistanceUnit::from_str(from).is_ok() && DistanceUnit::from_str(to).is_ok() {
-        Ok(AnyConverter::Distance(DistanceConverter))
-    } else if MassUnit::from_str(from).is_ok() && MassUnit::from_str(to).is_ok() {
-        Ok(AnyConverter::Mass(MassConverter))
-    } else if TemperatureUnit::from_str(from).is_ok() && TemperatureUnit::from_str(to).is_ok() {
-        Ok(AnyConverter::Temperature(TemperatureConverter))
-    } else {
-        Err(ConvertError::UnsupportedConversion(
+    let from_unit = CompoundUnit::from_str(from)?;
+    let to_unit = CompoundUnit::from_str(to)?;
+    let from_dimension = from_unit.dimension();
+    let to_dimension = to_unit.dimension();
+
+    if from_dimension != to_dimension {
+        return Err(ConvertError::IncompatibleDimensions {
+            from: from.to_string(),
+            from_dimension,
+            to: to.to_string(),
+            to_dimension,
+        });
+    }
+
+    let converter = match (from_unit.as_simple(), to_unit.as_simple()) {
+        (Some(_), Some(_)) => AnyConverter::for_dimension(from_dimension),
+        _ => None,
+    };
+    Ok(
+        converter.unwrap_or(AnyConverter::Compound(CompoundConverter::new(
+            from_dimension,
+        ))),
+    )
+}
+
+/// The target that stands for every unit of the value's kind, as in `5 kg -> all`.
+pub const ALL_UNITS: &str = "all";
+
+/// Every unit a value in `from` can be converted into, for an [`ALL_UNITS`] target.
+pub fn all_targets(from: &str) -> Result<Vec<String>, ConvertError> {
+    let units = match get_converter(from, from)? {
+        AnyConverter::Compound(_) => compound_targets(&CompoundUnit::from_str(from)?)?,
+        converter => converter.supported_units(),
+    };
+    if units.is_empty() {
+        return Err(ConvertError::UnsupportedConversion(
             from.to_string(),
-            to.to_string(),
-        ))
+            ALL_UNITS.to_string(),
+        ));
+    }
+    Ok(units)
+}
+
+/// The targets of a unit with no converter of its own, such as `N` or `m/s`. Every
+/// combination of units would be far too many, so these are the named derived units of its
+/// dimension with each SI prefix, then the unit with one of its units swapped at a time for
+/// another of the same kind: `km/s`, `ft/s`, `m/min` and so on.
+fn compound_targets(from: &CompoundUnit) -> Result<Vec<String>, ConvertError> {
+    let mut targets: Vec<String> = prefixed_derived_units(from.dimension())
+        .iter()
+        .map(AnyUnit::to_string)
+        .collect();
+    for (i, (unit, _)) in from.terms().iter().enumerate() {
+        for swapped in interchangeable_units(unit) {
+            let target = from.terms().iter().enumerate().try_fold(
+                CompoundUnit::default(),
+                |target, (j, (unit, power))| {
+                    let unit = if i == j { swapped } else { *unit };
+                    target * CompoundUnit::from(unit).powi(*power)?
+                },
+            )?;
+            targets.push(target.to_string());
+        }
+    }
+    let mut seen = HashSet::new();
+    targets.retain(|target| seen.insert(target.clone()));
+    Ok(targets)
+}
+
+/// The units that can take the place of `unit` in a compound unit.
+fn interchangeable_units(unit: &AnyUnit) -> Vec<AnyUnit> {
+    match unit {
+        AnyUnit::Distance(_) => DistanceUnit::iter()
+            .map(|unit| AnyUnit::Distance(unit.into()))
+            .collect(),
+        AnyUnit::Mass(_) => MassUnit::iter()
+            .map(|unit| AnyUnit::Mass(unit.into()))
+            .collect(),
+        AnyUnit::Time(_) => TimeUnit::iter()
+            .map(|unit| AnyUnit::Time(unit.into()))
+            .collect(),
+        AnyUnit::Derived(_) => prefixed_derived_units(unit.dimension()),
+        // Temperatures keep their scale
+        AnyUnit::Temperature(_) | AnyUnit::TemperatureDelta(_) => vec![*unit],
+    }
+}
+
+/// The named derived units of a dimension, unprefixed and with each SI prefix.
+fn prefixed_derived_units(dimension: Dimension) -> Vec<AnyUnit> {
+    DerivedUnit::iter()
+        .filter(|unit| unit.dimension() == dimension)
+        .flat_map(|unit| {
+            std::iter::once(None)
+                .chain(Prefix::iter().map(Some))
+                .map(move |prefix| AnyUnit::Derived(Prefixed::new(prefix, unit)))
+        })
+        .collect()
+}
+
+/// Any unit known to one of the converters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnyUnit {
+    Distance(Prefixed<DistanceUnit>),
+    Mass(Prefixed<MassUnit>),
+    Temperature(Prefixed<TemperatureUnit>),
+    TemperatureDelta(TemperatureDelta),
+    Time(Prefixed<TimeUnit>),
+    Derived(Prefixed<DerivedUnit>),
+}
+
+impl AnyUnit {
+    pub fn dimension(&self) -> Dimension {
+        match self {
+            AnyUnit::Distance(u) => u.unit.dimension(),
+            AnyUnit::Mass(u) => u.unit.dimension(),
+            AnyUnit::Temperature(u) => u.unit.dimension(),
+            AnyUnit::TemperatureDelta(u) => u.dimension(),
+            AnyUnit::Time(u) => u.unit.dimension(),
+            AnyUnit::Derived(u) => u.unit.dimension(),
+        }
+    }
+
+    /// The exact size of the unit in coherent SI base units.
+    pub fn exact_factor(&self) -> BigRational {
+        match self {
+            AnyUnit::Distance(u) => u.exact_factor(),
+            AnyUnit::Mass(u) => u.exact_factor(),
+            AnyUnit::Temperature(u) => u.exact_factor(),
+            AnyUnit::TemperatureDelta(u) => u.exact_factor(),
+            AnyUnit::Time(u) => u.exact_factor(),
+            AnyUnit::Derived(u) => u.exact_factor(),
+        }
+    }
+}
+
+impl FromStr for AnyUnit {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Deltas come before temperatures so that `dK` is a kelvin difference, not a decikelvin
+        let parsed = [
+            Prefixed::<DistanceUnit>::from_str(s).map(AnyUnit::Distance),
+            Prefixed::<MassUnit>::from_str(s).map(AnyUnit::Mass),
+            TemperatureDelta::from_str(s).map(AnyUnit::TemperatureDelta),
+            Prefixed::<TemperatureUnit>::from_str(s).map(AnyUnit::Temperature),
+            Prefixed::<TimeUnit>::from_str(s).map(AnyUnit::Time),
+            Prefixed::<DerivedUnit>::from_str(s).map(AnyUnit::Derived),
+        ];
+        // A unit's exact symbol beats an alias that ignores case, so that `TN` is a
+        // teranewton rather than the short ton `tn`
+        let units = || parsed.iter().flatten();
+        units()
+            .find(|unit| matches!(unit, AnyUnit::TemperatureDelta(_)) || unit.to_string() == s)
+            .or_else(|| units().next())
+            .copied()
+            .ok_or(ConvertError::InvalidUnit(s.to_string()))
+    }
+}
+
+impl Display for AnyUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AnyUnit::Distance(u) => write!(f, "{}", u),
+            AnyUnit::Mass(u) => write!(f, "{}", u),
+            AnyUnit::Temperature(u) => write!(f, "{}", u),
+            AnyUnit::TemperatureDelta(u) => write!(f, "{}", u),
+            AnyUnit::Time(u) => write!(f, "{}", u),
+            AnyUnit::Derived(u) => write!(f, "{}", u),
+        }
     }
 }
 
@@ -35,14 +217,104 @@ pub enum AnyConverter {
     Distance(DistanceConverter),
     Mass(MassConverter),
     Temperature(TemperatureConverter),
+    Time(TimeConverter),
+    Compound(CompoundConverter),
 }
 
 impl AnyConverter {
+    /// The converter handling units of the given dimension, if there is one.
+    pub fn for_dimension(dimension: Dimension) -> Option<AnyConverter> {
+        match dimension {
+            Dimension::LENGTH => Some(AnyConverter::Distance(DistanceConverter)),
+            Dimension::MASS => Some(AnyConverter::Mass(MassConverter)),
+            Dimension::TEMPERATURE => Some(AnyConverter::Temperature(TemperatureConverter)),
+            Dimension::TIME => Some(AnyConverter::Time(TimeConverter)),
+            _ => None,
+        }
+    }
+
     pub fn convert(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError> {
         match self {
             AnyConverter::Distance(c) => c.convert(value, from, to),
             AnyConverter::Mass(c) => c.convert(value, from, to),
             AnyConverter::Temperature(c) => c.convert(value, from, to),
+            AnyConverter::Time(c) => c.convert(value, from, to),
+            AnyConverter::Compound(c) => c.convert(value, from, to),
+        }
+    }
+
+    /// Converts without rejecting temperatures below absolute zero.
+    pub fn convert_unchecked(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError> {
+        match self {
+            AnyConverter::Temperature(c) => c.convert_unchecked(value, from, to),
+            _ => self.convert(value, from, to),
+        }
+    }
+
+    /// Converts the uncertainty of a measurement. An uncertainty is a spread of readings rather
+    /// than a reading, so it scales with the size of the units but ignores any offset: `± 1 °C`
+    /// is `± 1.8 °F`.
+    pub fn convert_uncertainty(
+        &self,
+        uncertainty: f64,
+        from: &str,
+        to: &str,
+    ) -> Result<f64, ConvertError> {
+        let converted = match self {
+            AnyConverter::Temperature(c) => c.convert_interval(uncertainty, from, to)?,
+            _ => self.convert(uncertainty, from, to)?,
+        };
+        // A scale that runs backwards, like Delisle, still has a positive spread
+        Ok(converted.abs())
+    }
+
+    /// Converts an exact rational value, composing the unit factors exactly so that no
+    /// rounding happens at all: `1 in` is exactly `127/5000 m`.
+    pub fn convert_exact(
+        &self,
+        value: &BigRational,
+        from: &str,
+        to: &str,
+    ) -> Result<BigRational, ConvertError> {
+        match self {
+            AnyConverter::Temperature(c) => c.convert_exact(value, from, to),
+            _ => CompoundConverter::new(self.dimension()).convert_exact(value, from, to),
+        }
+    }
+
+    /// Converts exactly without rejecting temperatures below absolute zero.
+    pub fn convert_exact_unchecked(
+        &self,
+        value: &BigRational,
+        from: &str,
+        to: &str,
+    ) -> Result<BigRational, ConvertError> {
+        match self {
+            AnyConverter::Temperature(c) => c.convert_exact_unchecked(value, from, to),
+            _ => self.convert_exact(value, from, to),
+        }
+    }
+
+    /// Converts a value written as a decimal string, e.g. `"0.1"`, without ever going through
+    /// `f64`, and writes the result back as a decimal. Results that do not terminate are
+    /// rounded to [`DECIMAL_DIGITS`](exact::DECIMAL_DIGITS) significant digits.
+    pub fn convert_decimal(
+        &self,
+        value: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<String, ConvertError> {
+        let result = self.convert_exact(&exact::parse_decimal(value)?, from, to)?;
+        Ok(exact::format_decimal(&result, exact::DECIMAL_DIGITS))
+    }
+
+    pub fn dimension(&self) -> Dimension {
+        match self {
+            AnyConverter::Distance(c) => c.dimension(),
+            AnyConverter::Mass(c) => c.dimension(),
+            AnyConverter::Temperature(c) => c.dimension(),
+            AnyConverter::Time(c) => c.dimension(),
+            AnyConverter::Compound(c) => c.dimension(),
         }
     }
 
@@ -51,6 +323,8 @@ impl AnyConverter {
             AnyConverter::Distance(c) => c.supported_units(),
             AnyConverter::Mass(c) => c.supported_units(),
             AnyConverter::Temperature(c) => c.supported_units(),
+            AnyConverter::Time(c) => c.supported_units(),
+            AnyConverter::Compound(c) => c.supported_units(),
         }
     }
 
@@ -59,6 +333,186 @@ impl AnyConverter {
             AnyConverter::Distance(c) => c.get_unit_string(unit_str),
             AnyConverter::Mass(c) => c.get_unit_string(unit_str),
             AnyConverter::Temperature(c) => c.get_unit_string(unit_str),
+            AnyConverter::Time(c) => c.get_unit_string(unit_str),
+            AnyConverter::Compound(c) => c.get_unit_string(unit_str),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::ConvertError, test_utils::assert_approx_eq};
+
+    use super::{
+        AnyConverter, all_targets,
+        dimension::Dimension,
+        exact::{decimal, from_f64, ratio, to_f64},
+        get_converter,
+    };
+
+    #[test]
+    fn test_get_converter_by_dimension() {
+        assert!(matches!(
+            get_converter("mi", "km"),
+            Ok(AnyConverter::Distance(_))
+        ));
+        assert!(matches!(
+            get_converter("dK", "dF"),
+            Ok(AnyConverter::Temperature(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_converter_compound_units() {
+        assert!(matches!(
+            get_converter("m/s", "km/h"),
+            Ok(AnyConverter::Compound(_))
+        ));
+        assert!(matches!(
+            get_converter("N", "kg·m/s²"),
+            Ok(AnyConverter::Compound(_))
+        ));
+        assert!(matches!(
+            get_converter("m·m/m", "ft"),
+            Ok(AnyConverter::Distance(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_converter_prefixed_units() {
+        assert!(matches!(
+            get_converter("GW", "kW"),
+            Ok(AnyConverter::Compound(_))
+        ));
+        assert!(matches!(
+            get_converter("ns", "ms"),
+            Ok(AnyConverter::Time(_))
+        ));
+        assert!(matches!(
+            get_converter("mK", "°C"),
+            Ok(AnyConverter::Temperature(_))
+        ));
+        // A teranewton, not a short ton
+        assert!(matches!(
+            get_converter("TN", "N"),
+            Ok(AnyConverter::Compound(_))
+        ));
+        assert!(matches!(
+            get_converter("tn", "lb"),
+            Ok(AnyConverter::Mass(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_converter_mismatched_dimensions() {
+        let err = get_converter("m", "kg").err().unwrap();
+        assert!(matches!(
+            err,
+            ConvertError::IncompatibleDimensions {
+                from_dimension: Dimension::LENGTH,
+                to_dimension: Dimension::MASS,
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Cannot convert 'm' (length) to 'kg' (mass)"
+        );
+    }
+
+    #[test]
+    fn test_get_converter_unknown_unit() {
+        assert_eq!(
+            get_converter("banana", "ft").err(),
+            Some(ConvertError::InvalidUnit("banana".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_all_targets() {
+        let units = all_targets("kg").unwrap();
+        assert!(units.contains(&"lb".to_string()));
+        assert!(units.contains(&"g".to_string()));
+        assert!(!units.contains(&"m".to_string()));
+        assert_eq!(all_targets("°F").unwrap(), all_targets("K").unwrap());
+
+        let units = all_targets("m/s").unwrap();
+        for unit in ["m/s", "km/s", "ft/s", "m/min", "m/h"] {
+            assert!(units.contains(&unit.to_string()), "{} is missing", unit);
+        }
+        let units = all_targets("N").unwrap();
+        assert_eq!(units[0], "N");
+        assert!(units.contains(&"kN".to_string()));
+        assert!(all_targets("kg·m/s²").unwrap().contains(&"MN".to_string()));
+    }
+
+    #[test]
+    fn test_convert_exact() {
+        let exact = |value: &str, from: &str, to: &str| {
+            get_converter(from, to)
+                .unwrap()
+                .convert_exact(&decimal(value), from, to)
+        };
+        assert_eq!(exact("1", "in", "m"), Ok(ratio(127, 5000)));
+        assert_eq!(exact("1", "m", "in"), Ok(ratio(5000, 127)));
+        assert_eq!(exact("3", "ft", "yd"), Ok(ratio(1, 1)));
+        assert_eq!(exact("100", "C", "F"), Ok(ratio(212, 1)));
+        assert_eq!(exact("-40", "F", "C"), Ok(ratio(-40, 1)));
+        assert_eq!(exact("18", "dF", "dC"), Ok(ratio(10, 1)));
+        assert_eq!(exact("36", "km/h", "m/s"), Ok(ratio(10, 1)));
+        assert!(matches!(
+            exact("-1", "K", "C"),
+            Err(ConvertError::BelowAbsoluteZero { .. })
+        ));
+    }
+
+    #[test]
+    fn test_conversions_round_once() {
+        for (from, to) in [("in", "m"), ("mi", "km"), ("lb", "g"), ("ftUS", "cm")] {
+            let there = get_converter(from, to).unwrap();
+            let back = get_converter(to, from).unwrap();
+            for value in ["0.1", "1", "3.3", "1234.5678"] {
+                let exact = there.convert_exact(&decimal(value), from, to).unwrap();
+                assert_eq!(back.convert_exact(&exact, to, from), Ok(decimal(value)));
+
+                let float = there.convert(value.parse().unwrap(), from, to).unwrap();
+                let exact_float = there
+                    .convert_exact(&from_f64(value.parse().unwrap()).unwrap(), from, to)
+                    .unwrap();
+                assert_eq!(float, to_f64(&exact_float), "{} {} -> {}", value, from, to);
+            }
+        }
+    }
+
+    #[test]
+    fn test_convert_decimal() {
+        let converter = get_converter("ft", "in").unwrap();
+        assert_eq!(
+            converter.convert_decimal("0.1", "ft", "in"),
+            Ok("1.2".to_string())
+        );
+        let converter = get_converter("m", "ft").unwrap();
+        assert_eq!(
+            converter.convert_decimal("1", "m", "ft"),
+            Ok("3.28083989501312335958005249344".to_string())
+        );
+        assert!(matches!(
+            converter.convert_decimal("1.2.3", "m", "ft"),
+            Err(ConvertError::ParseError(_))
+        ));
+    }
+
+    #[test]
+    fn test_convert_uncertainty() {
+        let uncertainty = |value: f64, from: &str, to: &str| {
+            get_converter(from, to)
+                .unwrap()
+                .convert_uncertainty(value, from, to)
+                .unwrap()
+        };
+        assert_approx_eq(uncertainty(0.2, "in", "mm"), 5.08, 1e-12);
+        assert_approx_eq(uncertainty(1.0, "C", "F"), 1.8, 1e-12);
+        assert_approx_eq(uncertainty(1.0, "C", "K"), 1.0, 1e-12);
+        assert_approx_eq(uncertainty(2.0, "C", "De"), 3.0, 1e-12);
+    }
+}
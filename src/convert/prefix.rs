@@ -0,0 +1,341 @@
+use std::{fmt::Display, str::FromStr};
+
+use num_rational::BigRational;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::error::ConvertError;
+
+use super::exact::{one, pow10};
+
+/// The SI metric prefixes, including the 2022 additions ronna, quetta, ronto and quecto.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum Prefix {
+    Quecto,
+    Ronto,
+    Yocto,
+    Zepto,
+    Atto,
+    Femto,
+    Pico,
+    Nano,
+    Micro,
+    Milli,
+    Centi,
+    Deci,
+    Deca,
+    Hecto,
+    Kilo,
+    Mega,
+    Giga,
+    Tera,
+    Peta,
+    Exa,
+    Zetta,
+    Yotta,
+    Ronna,
+    Quetta,
+}
+
+impl Prefix {
+    /// The power of ten the prefix stands for.
+    pub fn exponent(&self) -> i32 {
+        match self {
+            Prefix::Quecto => -30,
+            Prefix::Ronto => -27,
+            Prefix::Yocto => -24,
+            Prefix::Zepto => -21,
+            Prefix::Atto => -18,
+            Prefix::Femto => -15,
+            Prefix::Pico => -12,
+            Prefix::Nano => -9,
+            Prefix::Micro => -6,
+            Prefix::Milli => -3,
+            Prefix::Centi => -2,
+            Prefix::Deci => -1,
+            Prefix::Deca => 1,
+            Prefix::Hecto => 2,
+            Prefix::Kilo => 3,
+            Prefix::Mega => 6,
+            Prefix::Giga => 9,
+            Prefix::Tera => 12,
+            Prefix::Peta => 15,
+            Prefix::Exa => 18,
+            Prefix::Zetta => 21,
+            Prefix::Yotta => 24,
+            Prefix::Ronna => 27,
+            Prefix::Quetta => 30,
+        }
+    }
+
+    pub fn exact_factor(&self) -> BigRational {
+        pow10(self.exponent())
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Prefix::Quecto => "q",
+            Prefix::Ronto => "r",
+            Prefix::Yocto => "y",
+            Prefix::Zepto => "z",
+            Prefix::Atto => "a",
+            Prefix::Femto => "f",
+            Prefix::Pico => "p",
+            Prefix::Nano => "n",
+            Prefix::Micro => "µ",
+            Prefix::Milli => "m",
+            Prefix::Centi => "c",
+            Prefix::Deci => "d",
+            Prefix::Deca => "da",
+            Prefix::Hecto => "h",
+            Prefix::Kilo => "k",
+            Prefix::Mega => "M",
+            Prefix::Giga => "G",
+            Prefix::Tera => "T",
+            Prefix::Peta => "P",
+            Prefix::Exa => "E",
+            Prefix::Zetta => "Z",
+            Prefix::Yotta => "Y",
+            Prefix::Ronna => "R",
+            Prefix::Quetta => "Q",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Prefix::Quecto => "quecto",
+            Prefix::Ronto => "ronto",
+            Prefix::Yocto => "yocto",
+            Prefix::Zepto => "zepto",
+            Prefix::Atto => "atto",
+            Prefix::Femto => "femto",
+            Prefix::Pico => "pico",
+            Prefix::Nano => "nano",
+            Prefix::Micro => "micro",
+            Prefix::Milli => "milli",
+            Prefix::Centi => "centi",
+            Prefix::Deci => "deci",
+            Prefix::Deca => "deca",
+            Prefix::Hecto => "hecto",
+            Prefix::Kilo => "kilo",
+            Prefix::Mega => "mega",
+            Prefix::Giga => "giga",
+            Prefix::Tera => "tera",
+            Prefix::Peta => "peta",
+            Prefix::Exa => "exa",
+            Prefix::Zetta => "zetta",
+            Prefix::Yotta => "yotta",
+            Prefix::Ronna => "ronna",
+            Prefix::Quetta => "quetta",
+        }
+    }
+
+    /// Every way the prefix symbol may be written. Micro accepts the micro sign, the
+    /// Greek mu and a plain `u`.
+    fn symbols(&self) -> Vec<&'static str> {
+        match self {
+            Prefix::Micro => vec!["µ", "μ", "u"],
+            other => vec![other.symbol()],
+        }
+    }
+}
+
+impl Display for Prefix {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+/// A unit that SI prefixes can be attached to.
+pub trait Prefixable: FromStr<Err = ConvertError> + Display + Copy {
+    /// Whether the unit accepts a prefix. Only metric units do, so `km` parses but `kft`
+    /// does not.
+    fn is_prefixable(&self) -> bool;
+
+    /// The exact size of the unit in coherent SI units, e.g. `127/5000` for the inch.
+    fn exact_factor(&self) -> BigRational;
+}
+
+/// A unit with an optional SI prefix, e.g. `Mm`, `µs`, `GW` or a plain `ft`.
+///
+/// Prefix symbols are case-sensitive, so `Mm` is a megametre while `mm` is still a
+/// millimetre. A string is resolved in this order:
+///
+/// 1. the exact symbol of a unit (`mm`, `min`, `pc`);
+/// 2. a prefix symbol followed by the exact symbol of a prefixable unit (`Mm`, `kW`);
+/// 3. any alias of a unit, ignoring case (`Meters`), unless it is the symbol of a prefixed
+///    unit in another case: `NM` and `MG` are rejected rather than read as `nm` and `mg`;
+/// 4. a prefix name followed by any alias of a prefixable unit (`megametre`, `kilowatts`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Prefixed<U> {
+    pub prefix: Option<Prefix>,
+    pub unit: U,
+}
+
+impl<U> Prefixed<U> {
+    pub fn new(prefix: Option<Prefix>, unit: U) -> Self {
+        Prefixed { prefix, unit }
+    }
+}
+
+impl<U: Prefixable> Prefixed<U> {
+    /// The exact size of the prefixed unit in coherent SI units.
+    pub fn exact_factor(&self) -> BigRational {
+        self.prefix.map_or(one(), |prefix| prefix.exact_factor()) * self.unit.exact_factor()
+    }
+}
+
+impl<U> From<U> for Prefixed<U> {
+    fn from(unit: U) -> Self {
+        Prefixed::new(None, unit)
+    }
+}
+
+impl<U: Prefixable> FromStr for Prefixed<U> {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let exact = |candidate: &str| {
+            U::from_str(candidate)
+                .ok()
+                .filter(|unit| unit.to_string() == candidate)
+        };
+        if let Some(unit) = exact(s) {
+            return Ok(unit.into());
+        }
+
+        let mut by_symbol: Vec<(Prefix, &str)> = Prefix::iter()
+            .flat_map(|prefix| {
+                prefix
+                    .symbols()
+                    .into_iter()
+                    .map(move |symbol| (prefix, symbol))
+            })
+            .filter(|(_, symbol)| s.starts_with(symbol))
+            .collect();
+        // Try `da` before `d`
+        by_symbol.sort_by_key(|(_, symbol)| std::cmp::Reverse(symbol.len()));
+        for (prefix, symbol) in by_symbol {
+            if let Some(unit) = exact(&s[symbol.len()..]).filter(|unit| unit.is_prefixable()) {
+                return Ok(Prefixed::new(Some(prefix), unit));
+            }
+        }
+
+        if let Ok(unit) = U::from_str(s) {
+            // Case is what tells prefixes apart, so it cannot be ignored in a prefixed symbol
+            let symbol = unit.to_string();
+            if symbol.to_lowercase() == s.to_lowercase() && is_prefixed_symbol::<U>(&symbol) {
+                return Err(ConvertError::InvalidUnit(s.to_string()));
+            }
+            return Ok(unit.into());
+        }
+
+        let lower = s.to_lowercase();
+        Prefix::iter()
+            .find_map(|prefix| {
+                let rest = lower.strip_prefix(prefix.name())?;
+                U::from_str(rest)
+                    .ok()
+                    .filter(|unit| unit.is_prefixable())
+                    .map(|unit| Prefixed::new(Some(prefix), unit))
+            })
+            .ok_or(ConvertError::InvalidUnit(s.to_string()))
+    }
+}
+
+/// Whether a unit's symbol is a prefix symbol followed by the symbol of a prefixable unit,
+/// like `nm` or `kg`.
+fn is_prefixed_symbol<U: Prefixable>(symbol: &str) -> bool {
+    Prefix::iter()
+        .flat_map(|prefix| prefix.symbols())
+        .filter_map(|prefix_symbol| symbol.strip_prefix(prefix_symbol))
+        .any(|rest| {
+            U::from_str(rest).is_ok_and(|unit| unit.is_prefixable() && unit.to_string() == rest)
+        })
+}
+
+impl<U: Display> Display for Prefixed<U> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.prefix {
+            Some(prefix) => write!(f, "{}{}", prefix, self.unit),
+            None => write!(f, "{}", self.unit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{
+        convert::{derived::DerivedUnit, distance::DistanceUnit, mass::MassUnit, time::TimeUnit},
+        error::ConvertError,
+    };
+
+    use super::{Prefix, Prefixed, pow10};
+
+    #[test]
+    fn test_prefix_parsing() {
+        let tests = [
+            ("Mm", Some(Prefix::Mega), DistanceUnit::Meter),
+            ("mm", None, DistanceUnit::Millimeter),
+            ("Kilometres", None, DistanceUnit::Kilometer),
+            ("dam", Some(Prefix::Deca), DistanceUnit::Meter),
+            ("Qm", Some(Prefix::Quetta), DistanceUnit::Meter),
+            ("kpc", Some(Prefix::Kilo), DistanceUnit::Parsec),
+            ("megametres", Some(Prefix::Mega), DistanceUnit::Meter),
+        ];
+        for (input, prefix, unit) in tests {
+            assert_eq!(
+                Prefixed::<DistanceUnit>::from_str(input),
+                Ok(Prefixed::new(prefix, unit)),
+                "parsing '{}'",
+                input
+            );
+        }
+        assert_eq!(
+            Prefixed::<MassUnit>::from_str("Mg"),
+            Ok(Prefixed::new(Some(Prefix::Mega), MassUnit::Gram))
+        );
+        assert_eq!(
+            Prefixed::<MassUnit>::from_str("mg"),
+            Ok(Prefixed::new(None, MassUnit::Milligram))
+        );
+        assert_eq!(
+            Prefixed::<DerivedUnit>::from_str("GW"),
+            Ok(Prefixed::new(Some(Prefix::Giga), DerivedUnit::Watt))
+        );
+    }
+
+    #[test]
+    fn test_non_prefixable_units_are_rejected() {
+        assert_eq!(
+            Prefixed::<DistanceUnit>::from_str("kft"),
+            Err(ConvertError::InvalidUnit("kft".to_string()))
+        );
+        assert!(Prefixed::<DistanceUnit>::from_str("Mmi").is_err());
+    }
+
+    #[test]
+    fn test_prefix_case_is_not_ignored() {
+        for input in ["NM", "KM", "CM", "Km"] {
+            assert_eq!(
+                Prefixed::<DistanceUnit>::from_str(input),
+                Err(ConvertError::InvalidUnit(input.to_string())),
+            );
+        }
+        assert!(Prefixed::<MassUnit>::from_str("MG").is_err());
+        assert!(Prefixed::<TimeUnit>::from_str("MS").is_err());
+        assert_eq!(
+            Prefixed::<TimeUnit>::from_str("MIN"),
+            Ok(Prefixed::new(None, TimeUnit::Minute))
+        );
+    }
+
+    #[test]
+    fn test_prefix_display() {
+        let unit = Prefixed::new(Some(Prefix::Micro), DistanceUnit::Meter);
+        assert_eq!(unit.to_string(), "µm");
+        assert_eq!(Prefix::Ronna.exact_factor(), pow10(27));
+    }
+}
@@ -1,31 +1,32 @@
 use std::{collections::HashMap, fmt::Display, str::FromStr};
 
+use num_rational::BigRational;
+use num_traits::Signed;
 use once_cell::sync::Lazy;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
 use crate::error::ConvertError;
 
-use super::UnitConverter;
+use super::{
+    AnyUnit, UnitConverter,
+    definitions::definition,
+    dimension::Dimension,
+    exact::{from_f64, scale, to_f64},
+    prefix::{Prefixable, Prefixed},
+};
 
 pub struct TemperatureConverter;
 
-#[derive(Debug, Clone, Copy)]
-struct Celsius(f64);
-
-#[derive(Debug, Clone, Copy)]
-struct Fahrenheit(f64);
-
-#[derive(Debug, Clone, Copy)]
-struct Kelvin(f64);
+const ABSOLUTE_ZERO_TOLERANCE: f64 = 1e-9;
 
 impl UnitConverter for TemperatureConverter {
     fn convert(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError> {
-        let from_unit = TemperatureUnit::from_str(from)?;
-        let to_unit = TemperatureUnit::from_str(to)?;
+        self.convert_with_check(value, from, to, true)
+    }
 
-        let celsius = Self::to_celsius(value, &from_unit);
-        Ok(Self::from_celsius(celsius, &to_unit))
+    fn dimension(&self) -> Dimension {
+        Dimension::TEMPERATURE
     }
 
     fn supported_units(&self) -> Vec<String> {
@@ -37,53 +38,145 @@ impl UnitConverter for TemperatureConverter {
 
 impl TemperatureConverter {
     pub fn get_unit_string(&self, unit_str: &str) -> String {
-        if let Ok(unit) = TemperatureUnit::from_str(unit_str) {
+        if let Ok(delta) = TemperatureDelta::from_str(unit_str) {
+            delta.to_string()
+        } else if let Ok(unit) = Prefixed::<TemperatureUnit>::from_str(unit_str) {
             unit.to_string()
         } else {
             unit_str.to_string()
         }
     }
 
-    fn to_celsius(value: f64, unit: &TemperatureUnit) -> Celsius {
-        match unit {
-            TemperatureUnit::Celsius => Celsius(value),
-            TemperatureUnit::Fahrenheit => Fahrenheit(value).into(),
-            TemperatureUnit::Kelvin => Kelvin(value).into(),
+    /// Converts like [`UnitConverter::convert`] but without rejecting temperatures below
+    /// absolute zero, for callers deliberately doing arithmetic on offset scales.
+    pub fn convert_unchecked(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError> {
+        self.convert_with_check(value, from, to, false)
+    }
+
+    fn convert_with_check(
+        &self,
+        value: f64,
+        from: &str,
+        to: &str,
+        check_absolute_zero: bool,
+    ) -> Result<f64, ConvertError> {
+        let from_delta = TemperatureDelta::from_str(from);
+        let to_delta = TemperatureDelta::from_str(to);
+        match (from_delta, to_delta) {
+            (Ok(from_delta), Ok(to_delta)) => Ok(Self::interval(
+                value,
+                &from_delta.0.into(),
+                &to_delta.0.into(),
+            )),
+            (Err(_), Err(_)) => {
+                let from_unit = Prefixed::<TemperatureUnit>::from_str(from)?;
+                let to_unit = Prefixed::<TemperatureUnit>::from_str(to)?;
+
+                let kelvin = Self::to_kelvin(&from_f64(value)?, &from_unit);
+                // Allow for rounding when converting exactly absolute zero between scales
+                if check_absolute_zero && to_f64(&kelvin) < -ABSOLUTE_ZERO_TOLERANCE {
+                    return Err(ConvertError::BelowAbsoluteZero {
+                        value,
+                        unit: from_unit.to_string(),
+                    });
+                }
+                Ok(to_f64(&Self::from_kelvin(kelvin, &to_unit)))
+            }
+            _ => Err(ConvertError::UnsupportedConversion(
+                from.to_string(),
+                to.to_string(),
+            )),
         }
     }
 
-    fn from_celsius(celsius: Celsius, unit: &TemperatureUnit) -> f64 {
-        match unit {
-            TemperatureUnit::Celsius => celsius.0,
-            TemperatureUnit::Fahrenheit => Fahrenheit::from(celsius).0,
-            TemperatureUnit::Kelvin => Kelvin::from(celsius).0,
+    /// Converts exactly, like [`AnyConverter::convert_exact`](super::AnyConverter::convert_exact).
+    /// Temperatures below absolute zero are rejected.
+    pub fn convert_exact(
+        &self,
+        value: &BigRational,
+        from: &str,
+        to: &str,
+    ) -> Result<BigRational, ConvertError> {
+        self.convert_exact_with_check(value, from, to, true)
+    }
+
+    /// Converts exactly, without rejecting temperatures below absolute zero.
+    pub fn convert_exact_unchecked(
+        &self,
+        value: &BigRational,
+        from: &str,
+        to: &str,
+    ) -> Result<BigRational, ConvertError> {
+        self.convert_exact_with_check(value, from, to, false)
+    }
+
+    fn convert_exact_with_check(
+        &self,
+        value: &BigRational,
+        from: &str,
+        to: &str,
+        check_absolute_zero: bool,
+    ) -> Result<BigRational, ConvertError> {
+        match (
+            TemperatureDelta::from_str(from),
+            TemperatureDelta::from_str(to),
+        ) {
+            (Ok(from_delta), Ok(to_delta)) => {
+                Ok(value * from_delta.exact_factor() / to_delta.exact_factor())
+            }
+            (Err(_), Err(_)) => {
+                let from_unit = Prefixed::<TemperatureUnit>::from_str(from)?;
+                let to_unit = Prefixed::<TemperatureUnit>::from_str(to)?;
+
+                let kelvin = Self::to_kelvin(value, &from_unit);
+                if check_absolute_zero && kelvin.is_negative() {
+                    return Err(ConvertError::BelowAbsoluteZero {
+                        value: to_f64(value),
+                        unit: from_unit.to_string(),
+                    });
+                }
+                Ok(Self::from_kelvin(kelvin, &to_unit))
+            }
+            _ => Err(ConvertError::UnsupportedConversion(
+                from.to_string(),
+                to.to_string(),
+            )),
         }
     }
-}
 
-// Convert to Celsius
-impl From<Fahrenheit> for Celsius {
-    fn from(value: Fahrenheit) -> Self {
-        Celsius((value.0 - 32.0) * 5.0 / 9.0)
+    /// A reading on the given scale as an exact temperature in kelvin.
+    fn to_kelvin(value: &BigRational, unit: &Prefixed<TemperatureUnit>) -> BigRational {
+        let definition = definition(&AnyUnit::Temperature(*unit));
+        value * unit.exact_factor() + &definition.offset
     }
-}
 
-impl From<Kelvin> for Celsius {
-    fn from(value: Kelvin) -> Self {
-        Celsius(value.0 - 273.15)
+    fn from_kelvin(kelvin: BigRational, unit: &Prefixed<TemperatureUnit>) -> BigRational {
+        let definition = definition(&AnyUnit::Temperature(*unit));
+        (kelvin - &definition.offset) / unit.exact_factor()
     }
-}
 
-// Convert from Celsius
-impl From<Celsius> for Fahrenheit {
-    fn from(value: Celsius) -> Self {
-        Fahrenheit((value.0 * 9.0 / 5.0) + 32.0)
+    /// Converts a temperature difference rather than an absolute temperature, so only the
+    /// size of a degree matters and the scale offsets are ignored (a change of 10 °C is a
+    /// change of 18 °F). Both plain and delta unit strings are accepted.
+    pub fn convert_interval(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError> {
+        let from_unit = Self::interval_unit(from)?;
+        let to_unit = Self::interval_unit(to)?;
+        Ok(Self::interval(value, &from_unit, &to_unit))
+    }
+
+    fn interval_unit(unit_str: &str) -> Result<Prefixed<TemperatureUnit>, ConvertError> {
+        TemperatureDelta::from_str(unit_str)
+            .map(|delta| delta.0.into())
+            .or_else(|_| Prefixed::<TemperatureUnit>::from_str(unit_str))
     }
-}
 
-impl From<Celsius> for Kelvin {
-    fn from(value: Celsius) -> Self {
-        Kelvin(value.0 + 273.15)
+    fn interval(
+        value: f64,
+        from: &Prefixed<TemperatureUnit>,
+        to: &Prefixed<TemperatureUnit>,
+    ) -> f64 {
+        // Every scale is affine in every other, so the offset cancels out in the difference
+        scale(value, &(from.exact_factor() / to.exact_factor()))
     }
 }
 
@@ -92,14 +185,35 @@ pub enum TemperatureUnit {
     Celsius,
     Fahrenheit,
     Kelvin,
+    Rankine,
+    Reaumur,
+    Delisle,
+    Newton,
+    Romer,
 }
 
 impl TemperatureUnit {
+    pub fn dimension(&self) -> Dimension {
+        Dimension::TEMPERATURE
+    }
+
     pub fn accepted_string() -> Vec<&'static str> {
         TEMPERATURE_UNIT_STRINGS.keys().copied().collect()
     }
 }
 
+impl Prefixable for TemperatureUnit {
+    fn is_prefixable(&self) -> bool {
+        matches!(self, TemperatureUnit::Kelvin)
+    }
+
+    fn exact_factor(&self) -> BigRational {
+        definition(&AnyUnit::Temperature((*self).into()))
+            .factor
+            .clone()
+    }
+}
+
 impl FromStr for TemperatureUnit {
     type Err = ConvertError;
 
@@ -116,28 +230,94 @@ impl Display for TemperatureUnit {
         match self {
             TemperatureUnit::Celsius => write!(f, "°C"),
             TemperatureUnit::Fahrenheit => write!(f, "°F"),
-            TemperatureUnit::Kelvin => write!(f, "°K"),
+            TemperatureUnit::Kelvin => write!(f, "K"),
+            TemperatureUnit::Rankine => write!(f, "°R"),
+            TemperatureUnit::Reaumur => write!(f, "°Ré"),
+            TemperatureUnit::Delisle => write!(f, "°De"),
+            TemperatureUnit::Newton => write!(f, "°N"),
+            TemperatureUnit::Romer => write!(f, "°Rø"),
         }
     }
 }
 
+/// A temperature difference measured in degrees of the wrapped scale, e.g. `Δ°C` or `dK`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TemperatureDelta(pub TemperatureUnit);
+
+impl TemperatureDelta {
+    pub fn dimension(&self) -> Dimension {
+        Dimension::TEMPERATURE
+    }
+
+    pub fn exact_factor(&self) -> BigRational {
+        self.0.exact_factor()
+    }
+}
+
+impl FromStr for TemperatureDelta {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lower = s.to_lowercase();
+        let unit_str = ["δ", "delta", "d"]
+            .iter()
+            .find_map(|prefix| lower.strip_prefix(prefix))
+            .ok_or(ConvertError::InvalidUnit(s.to_string()))?;
+        TemperatureUnit::from_str(unit_str.trim())
+            .map(TemperatureDelta)
+            .map_err(|_| ConvertError::InvalidUnit(s.to_string()))
+    }
+}
+
+impl Display for TemperatureDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Δ{}", self.0)
+    }
+}
+
 static TEMPERATURE_UNIT_STRINGS: Lazy<HashMap<&'static str, TemperatureUnit>> = Lazy::new(|| {
     use TemperatureUnit::*;
     let mut map = HashMap::new();
     map.insert("c", Celsius);
+    map.insert("°c", Celsius);
     map.insert("celsius", Celsius);
     map.insert("f", Fahrenheit);
+    map.insert("°f", Fahrenheit);
     map.insert("fahrenheit", Fahrenheit);
     map.insert("k", Kelvin);
     map.insert("kelvin", Kelvin);
+    map.insert("r", Rankine);
+    map.insert("°r", Rankine);
+    map.insert("rankine", Rankine);
+    map.insert("ré", Reaumur);
+    map.insert("°ré", Reaumur);
+    map.insert("°re", Reaumur);
+    map.insert("réaumur", Reaumur);
+    map.insert("reaumur", Reaumur);
+    map.insert("de", Delisle);
+    map.insert("°de", Delisle);
+    map.insert("delisle", Delisle);
+    map.insert("°n", Newton);
+    map.insert("degrees newton", Newton);
+    map.insert("rø", Romer);
+    map.insert("°rø", Romer);
+    map.insert("rømer", Romer);
+    map.insert("romer", Romer);
+    map.insert("roemer", Romer);
     map
 });
 
 #[cfg(test)]
 mod tests {
-    use crate::{convert::UnitConverter, test_utils::assert_approx_eq};
+    use std::str::FromStr;
 
-    use super::TemperatureConverter;
+    use crate::{
+        convert::UnitConverter,
+        error::ConvertError,
+        test_utils::{assert_approx_eq, assert_convert_error},
+    };
+
+    use super::{TemperatureConverter, TemperatureDelta, TemperatureUnit};
 
     #[test]
     fn basic_temperature_conversions() {
@@ -152,4 +332,94 @@ mod tests {
             assert_approx_eq(result, expected, 1e-4);
         }
     }
+
+    #[test]
+    fn historical_scale_conversions() {
+        let tests = [
+            (100.0, "c", "rankine", 671.67),
+            (0.0, "r", "k", 0.0),
+            (100.0, "c", "réaumur", 80.0),
+            (0.0, "c", "delisle", 150.0),
+            (0.0, "de", "c", 100.0),
+            (100.0, "c", "°n", 33.0),
+            (100.0, "c", "rømer", 60.0),
+            (7.5, "rø", "c", 0.0),
+        ];
+        let converter = TemperatureConverter;
+        for (value, from, to, expected) in tests {
+            let result = converter.convert(value, from, to).unwrap();
+            assert_approx_eq(result, expected, 1e-9);
+        }
+    }
+
+    #[test]
+    fn interval_conversions() {
+        let converter = TemperatureConverter;
+        assert_approx_eq(
+            converter.convert_interval(10.0, "c", "f").unwrap(),
+            18.0,
+            1e-9,
+        );
+        assert_approx_eq(
+            converter.convert_interval(9.0, "r", "k").unwrap(),
+            5.0,
+            1e-9,
+        );
+        assert_approx_eq(
+            converter.convert(10.0, "Δ°C", "delta F").unwrap(),
+            18.0,
+            1e-9,
+        );
+        assert_approx_eq(converter.convert(1.0, "dK", "dC").unwrap(), 1.0, 1e-9);
+    }
+
+    #[test]
+    fn delta_and_absolute_units_do_not_mix() {
+        assert_convert_error(TemperatureConverter, "dC", "F", 10.0, |e| {
+            matches!(e, ConvertError::UnsupportedConversion(_, _))
+        });
+    }
+
+    #[test]
+    fn delta_unit_parsing() {
+        assert_eq!(
+            TemperatureDelta::from_str("Δ°C"),
+            Ok(TemperatureDelta(TemperatureUnit::Celsius))
+        );
+        assert_eq!(
+            TemperatureDelta::from_str("delta F"),
+            Ok(TemperatureDelta(TemperatureUnit::Fahrenheit))
+        );
+        assert!(TemperatureDelta::from_str("de").is_err());
+        assert_eq!(TemperatureConverter.get_unit_string("dK"), "ΔK");
+    }
+
+    #[test]
+    fn below_absolute_zero_is_rejected() {
+        assert_convert_error(
+            TemperatureConverter,
+            "k",
+            "c",
+            -500.0,
+            |e| matches!(e, ConvertError::BelowAbsoluteZero { value, .. } if *value == -500.0),
+        );
+        assert_convert_error(TemperatureConverter, "c", "f", -300.0, |e| {
+            matches!(e, ConvertError::BelowAbsoluteZero { .. })
+        });
+        let result = TemperatureConverter.convert(-459.67, "f", "k").unwrap();
+        assert_approx_eq(result, 0.0, 1e-9);
+    }
+
+    #[test]
+    fn unchecked_conversion_allows_below_absolute_zero() {
+        let result = TemperatureConverter
+            .convert_unchecked(-500.0, "k", "c")
+            .unwrap();
+        assert_approx_eq(result, -773.15, 1e-9);
+    }
+
+    #[test]
+    fn kelvin_has_no_degree_sign() {
+        assert_eq!(TemperatureConverter.get_unit_string("k"), "K");
+    }
 }
@@ -0,0 +1,178 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use num_rational::BigRational;
+use once_cell::sync::Lazy;
+use strum::IntoEnumIterator;
+use strum_macros::EnumIter;
+
+use crate::error::ConvertError;
+
+use super::{
+    AnyUnit, UnitConverter,
+    definitions::definition,
+    dimension::Dimension,
+    exact::scale,
+    prefix::{Prefixable, Prefixed},
+    typed::static_units,
+};
+
+pub struct TimeConverter;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Second;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Minute;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Hour;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Day;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Week;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Year;
+
+static_units!(
+    TimeUnit, Second:
+    Second, Minute, Hour, Day, Week, Year,
+);
+
+impl TimeConverter {
+    pub fn get_unit_string(&self, unit_str: &str) -> String {
+        if let Ok(unit) = Prefixed::<TimeUnit>::from_str(unit_str) {
+            unit.to_string()
+        } else {
+            unit_str.to_string()
+        }
+    }
+}
+
+impl UnitConverter for TimeConverter {
+    fn convert(&self, value: f64, from: &str, to: &str) -> Result<f64, ConvertError> {
+        let from_unit = Prefixed::<TimeUnit>::from_str(from)?;
+        let to_unit = Prefixed::<TimeUnit>::from_str(to)?;
+
+        let factor = from_unit.exact_factor() / to_unit.exact_factor();
+        Ok(scale(value, &factor))
+    }
+
+    fn dimension(&self) -> Dimension {
+        Dimension::TIME
+    }
+
+    fn supported_units(&self) -> Vec<String> {
+        let mut units: Vec<String> = TimeUnit::iter().map(|unit| unit.to_string()).collect();
+        units.sort();
+        units
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter)]
+pub enum TimeUnit {
+    Second,
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Year,
+}
+
+impl TimeUnit {
+    pub fn dimension(&self) -> Dimension {
+        Dimension::TIME
+    }
+
+    pub fn accepted_string() -> Vec<&'static str> {
+        TIME_UNIT_STRINGS.keys().copied().collect()
+    }
+}
+
+impl Prefixable for TimeUnit {
+    fn is_prefixable(&self) -> bool {
+        matches!(self, TimeUnit::Second | TimeUnit::Year)
+    }
+
+    fn exact_factor(&self) -> BigRational {
+        definition(&AnyUnit::Time((*self).into())).factor.clone()
+    }
+}
+
+impl FromStr for TimeUnit {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TIME_UNIT_STRINGS
+            .get(s.to_lowercase().as_str())
+            .copied()
+            .ok_or(ConvertError::InvalidUnit(s.to_string()))
+    }
+}
+
+impl Display for TimeUnit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeUnit::Second => write!(f, "s"),
+            TimeUnit::Minute => write!(f, "min"),
+            TimeUnit::Hour => write!(f, "h"),
+            TimeUnit::Day => write!(f, "d"),
+            TimeUnit::Week => write!(f, "wk"),
+            TimeUnit::Year => write!(f, "yr"),
+        }
+    }
+}
+
+static TIME_UNIT_STRINGS: Lazy<HashMap<&'static str, TimeUnit>> = Lazy::new(|| {
+    use TimeUnit::*;
+    let mut map = HashMap::new();
+    map.insert("s", Second);
+    map.insert("sec", Second);
+    map.insert("secs", Second);
+    map.insert("second", Second);
+    map.insert("seconds", Second);
+    map.insert("min", Minute);
+    map.insert("mins", Minute);
+    map.insert("minute", Minute);
+    map.insert("minutes", Minute);
+    map.insert("h", Hour);
+    map.insert("hr", Hour);
+    map.insert("hrs", Hour);
+    map.insert("hour", Hour);
+    map.insert("hours", Hour);
+    map.insert("d", Day);
+    map.insert("day", Day);
+    map.insert("days", Day);
+    map.insert("wk", Week);
+    map.insert("week", Week);
+    map.insert("weeks", Week);
+    map.insert("yr", Year);
+    map.insert("yrs", Year);
+    map.insert("year", Year);
+    map.insert("years", Year);
+    map
+});
+
+#[cfg(test)]
+mod tests {
+    use crate::{convert::UnitConverter, test_utils::assert_approx_eq};
+
+    use super::TimeConverter;
+
+    #[test]
+    fn test_time_conversions() {
+        let tests = [
+            (1.0, "h", "s", 3600.0),
+            (1.0, "day", "min", 1440.0),
+            (2.0, "weeks", "days", 14.0),
+            (1.0, "yr", "d", 365.25),
+        ];
+        let converter = TimeConverter;
+        for (value, from, to, expected) in tests {
+            let result = converter.convert(value, from, to).unwrap();
+            assert_approx_eq(result, expected, 1e-9);
+        }
+    }
+}
@@ -0,0 +1,228 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    marker::PhantomData,
+    ops::{Add, Div, Mul, Sub},
+};
+
+use num_rational::BigRational;
+
+use crate::{format::format_value, quantity::Quantity};
+
+use super::{distance::Meter, exact::scale, mass::Gram, time::Second};
+
+/// A unit known at compile time, such as [`Meter`] or [`Pound`](super::mass::Pound).
+///
+/// Every marker type in the distance, mass and time converters implements this, so it can
+/// be used as the unit parameter of [`Length`], [`Mass`] or [`Time`]. The markers hold no
+/// value; the value lives in the typed quantity.
+pub trait StaticUnit: Copy {
+    /// The base unit of the unit's dimension, which keeps a mass unit out of a [`Length`].
+    type Base;
+
+    /// The unit's symbol, as the runtime converters write it.
+    fn symbol() -> String;
+
+    /// The exact size of the unit in coherent SI units, looked up once and then kept.
+    fn exact_factor() -> &'static BigRational;
+}
+
+/// Implements [`StaticUnit`] for newtypes whose names match the variants of `$unit`.
+macro_rules! static_units {
+    ($unit:ident, $base:ident: $($name:ident),+ $(,)?) => {
+        $(
+            impl $crate::convert::typed::StaticUnit for $name {
+                type Base = $base;
+
+                fn symbol() -> String {
+                    $unit::$name.to_string()
+                }
+
+                fn exact_factor() -> &'static num_rational::BigRational {
+                    static FACTOR: once_cell::sync::Lazy<num_rational::BigRational> =
+                        once_cell::sync::Lazy::new(|| {
+                            $crate::convert::prefix::Prefixable::exact_factor(&$unit::$name)
+                        });
+                    &FACTOR
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use static_units;
+
+/// Defines a quantity type that is generic over the units of one dimension, whose base
+/// unit `$base` marks which units belong to it.
+macro_rules! typed_quantity {
+    ($(#[$doc:meta])* $name:ident, $base:ty) => {
+        $(#[$doc])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name<U> {
+            value: f64,
+            unit: PhantomData<U>,
+        }
+
+        impl<U> $name<U> {
+            pub fn new(value: f64) -> Self {
+                $name {
+                    value,
+                    unit: PhantomData,
+                }
+            }
+
+            pub fn value(&self) -> f64 {
+                self.value
+            }
+        }
+
+        impl<U: StaticUnit<Base = $base>> $name<U> {
+            /// Converts into another unit of the same dimension, with a single exact
+            /// multiplication.
+            pub fn to<V: StaticUnit<Base = $base>>(self) -> $name<V> {
+                $name::new(scale(self.value, &(U::exact_factor() / V::exact_factor())))
+            }
+        }
+
+        impl<U, V> Add<$name<V>> for $name<U>
+        where
+            U: StaticUnit<Base = $base>,
+            V: StaticUnit<Base = $base>,
+        {
+            type Output = $name<U>;
+
+            fn add(self, rhs: $name<V>) -> Self::Output {
+                $name::new(self.value + rhs.to::<U>().value)
+            }
+        }
+
+        impl<U, V> Sub<$name<V>> for $name<U>
+        where
+            U: StaticUnit<Base = $base>,
+            V: StaticUnit<Base = $base>,
+        {
+            type Output = $name<U>;
+
+            fn sub(self, rhs: $name<V>) -> Self::Output {
+                $name::new(self.value - rhs.to::<U>().value)
+            }
+        }
+
+        impl<U> Mul<f64> for $name<U> {
+            type Output = $name<U>;
+
+            fn mul(self, rhs: f64) -> Self::Output {
+                $name::new(self.value * rhs)
+            }
+        }
+
+        impl<U> Div<f64> for $name<U> {
+            type Output = $name<U>;
+
+            fn div(self, rhs: f64) -> Self::Output {
+                $name::new(self.value / rhs)
+            }
+        }
+
+        impl<U, V> PartialEq<$name<V>> for $name<U>
+        where
+            U: StaticUnit<Base = $base>,
+            V: StaticUnit<Base = $base>,
+        {
+            fn eq(&self, other: &$name<V>) -> bool {
+                self.value == other.to::<U>().value
+            }
+        }
+
+        impl<U, V> PartialOrd<$name<V>> for $name<U>
+        where
+            U: StaticUnit<Base = $base>,
+            V: StaticUnit<Base = $base>,
+        {
+            fn partial_cmp(&self, other: &$name<V>) -> Option<Ordering> {
+                self.value.partial_cmp(&other.to::<U>().value)
+            }
+        }
+
+        impl<U: StaticUnit> Display for $name<U> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{} {}", format_value(self.value), U::symbol())
+            }
+        }
+
+        impl<U: StaticUnit> From<$name<U>> for Quantity {
+            fn from(quantity: $name<U>) -> Self {
+                Quantity::new(quantity.value, &U::symbol())
+                    .expect("the symbols of static units parse")
+            }
+        }
+    };
+}
+
+typed_quantity!(
+    /// A length in a unit fixed at compile time, e.g. `Length<Meter>` or `Length<Foot>`.
+    ///
+    /// Lengths in different units can be added and compared, but a length can never be
+    /// added to a [`Mass`], as the two are different types.
+    Length,
+    Meter
+);
+
+typed_quantity!(
+    /// A mass in a unit fixed at compile time, e.g. `Mass<Kilogram>` or `Mass<Pound>`.
+    Mass,
+    Gram
+);
+
+typed_quantity!(
+    /// A duration in a unit fixed at compile time, e.g. `Time<Second>` or `Time<Hour>`.
+    Time,
+    Second
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        convert::{
+            distance::{Centimeter, Foot, Inch, Kilometer, Meter, Mile, Yard},
+            mass::{Kilogram, Pound},
+            time::{Hour, Minute},
+        },
+        quantity::Quantity,
+        test_utils::assert_approx_eq,
+    };
+
+    use super::{Length, Mass, Time};
+
+    #[test]
+    fn test_static_conversion() {
+        let distance: Length<Kilometer> = Length::<Mile>::new(1.0).to();
+        assert_approx_eq(distance.value(), 1.60934, 1e-5);
+
+        let mass = Mass::<Pound>::new(2.0).to::<Kilogram>();
+        assert_approx_eq(mass.value(), 0.907186, 1e-5);
+        assert_eq!(Time::<Hour>::new(1.5).to::<Minute>().value(), 90.0);
+        // One exact factor, so there is no rounding between the two units
+        assert_eq!(Length::<Inch>::new(1.0).to::<Centimeter>().value(), 2.54);
+        assert_eq!(Length::<Foot>::new(3.0).to::<Yard>().value(), 1.0);
+    }
+
+    #[test]
+    fn test_static_arithmetic() {
+        let height = Length::<Foot>::new(5.0) + Length::<Inch>::new(6.0);
+        assert_approx_eq(height.value(), 5.5, 1e-9);
+        assert_eq!(height.to_string(), "5.5 ft");
+
+        let half = Length::<Meter>::new(3.0) / 2.0;
+        assert_eq!(half.value(), 1.5);
+        assert!(Length::<Mile>::new(1.0) > Length::<Meter>::new(1500.0));
+        assert!(Length::<Meter>::new(1000.0) == Length::<Kilometer>::new(1.0));
+    }
+
+    #[test]
+    fn test_into_quantity() {
+        let quantity = Quantity::from(Mass::<Kilogram>::new(2.0));
+        assert_eq!(quantity.to_string(), "2 kg");
+        assert_approx_eq(quantity.to("lb").unwrap().value(), 4.40924, 1e-5);
+    }
+}
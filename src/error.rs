@@ -1,5 +1,7 @@
 use thiserror::Error;
 
+use crate::convert::dimension::Dimension;
+
 #[derive(Debug, Error)]
 pub enum ConvertError {
     #[error("Invalid unit: '{0}'")]
@@ -8,6 +10,17 @@ pub enum ConvertError {
     #[error("Conversion from '{0}' to '{1}' not supported")]
     UnsupportedConversion(String, String),
 
+    #[error("Cannot convert '{from}' ({from_dimension}) to '{to}' ({to_dimension})")]
+    IncompatibleDimensions {
+        from: String,
+        from_dimension: Dimension,
+        to: String,
+        to_dimension: Dimension,
+    },
+
+    #[error("{value} {unit} is below absolute zero")]
+    BelowAbsoluteZero { value: f64, unit: String },
+
     #[error("Error parsing an expression: {0}")]
     ParseError(String),
 
@@ -21,6 +34,18 @@ impl PartialEq for ConvertError {
         match (self, other) {
             (InvalidUnit(a), InvalidUnit(b)) => a == b,
             (UnsupportedConversion(a1, a2), UnsupportedConversion(b1, b2)) => a1 == b1 && a2 == b2,
+            (
+                IncompatibleDimensions {
+                    from: f1, to: t1, ..
+                },
+                IncompatibleDimensions {
+                    from: f2, to: t2, ..
+                },
+            ) => f1 == f2 && t1 == t2,
+            (
+                BelowAbsoluteZero { value: a, unit: u1 },
+                BelowAbsoluteZero { value: b, unit: u2 },
+            ) => a == b && u1 == u2,
             (ParseError(a), ParseError(b)) => a == b,
             (IoError(_), IoError(_)) => false,
             _ => false,
@@ -1,16 +1,75 @@
+use std::str::FromStr;
+
+use num_rational::BigRational;
+use num_traits::Zero;
 use strsim::levenshtein;
 
 use crate::{
-    convert::{distance::DistanceUnit, mass::MassUnit, temperature::TemperatureUnit},
+    convert::{
+        ALL_UNITS, AnyUnit,
+        best::BestUnit,
+        compound::CompoundUnit,
+        derived::DerivedUnit,
+        distance::DistanceUnit,
+        exact::{DECIMAL_DIGITS, format_decimal, parse_decimal, to_f64},
+        get_converter,
+        mass::MassUnit,
+        mixed::MixedUnit,
+        temperature::TemperatureUnit,
+        time::TimeUnit,
+    },
     error::ConvertError,
+    format::{Formatter, Reading, Rounding, decimal_places},
 };
 
 /// Represents a parsed conversion expression.
 #[derive(Debug)]
 pub struct ParsedExpression {
     pub value: f64,
+    /// The value as written, which sets the precision of the result. For a quantity in several
+    /// parts this is their total to the places of the last part, e.g. `4` for `1 m 1 ft`.
+    pub value_text: String,
+    /// The exact total of a quantity in several parts, e.g. `1 m 1 ft` in feet, which
+    /// `value_text` only approximates. A single value gives `None`, as its text is exact.
+    pub total: Option<BigRational>,
+    /// The `±` tolerance written after the value, e.g. `0.2` in `10.0 ± 0.2 in`.
+    pub uncertainty: Option<f64>,
     pub from: String,
-    pub to: String,
+    /// The value and unit of each part as written: `5 ft 11 in` has two, which are added up
+    /// into `value` and `from`.
+    pub parts: Vec<(String, String)>,
+    /// The units to convert into, one result each: `10 km -> mi, ft` has two.
+    pub to: Vec<String>,
+}
+
+impl ParsedExpression {
+    /// The value to convert, with the exact total of a quantity in several parts.
+    pub fn reading(&self) -> Reading<'_> {
+        Reading {
+            text: &self.value_text,
+            total: self.total.as_ref(),
+        }
+    }
+
+    /// The quantity as written when it has several parts, e.g. `5 ft 11 in`, with each number
+    /// laid out by `formatter`. A quantity in a single unit gives `None`.
+    pub fn mixed_input(&self, formatter: &Formatter) -> Option<String> {
+        if self.parts.len() < 2 {
+            return None;
+        }
+        let parts: Vec<String> = self
+            .parts
+            .iter()
+            .map(|(value, unit)| {
+                let unit = get_converter(unit, unit).map_or_else(
+                    |_| unit.clone(),
+                    |converter| converter.get_unit_string(unit),
+                );
+                format!("{} {}", formatter.format_written(value), unit)
+            })
+            .collect();
+        Some(parts.join(" "))
+    }
 }
 
 pub fn parse_expression(expr: &str) -> Result<ParsedExpression, ConvertError> {
@@ -30,59 +89,193 @@ pub fn parse_expression(expr: &str) -> Result<ParsedExpression, ConvertError> {
     // Extract value+from and to
     let (left, right) = (parts[0], parts[1]);
 
-    let (value, from_unit) = parse_value_and_unit(left)?;
-    let to_unit = right.to_string();
-    let from_unit = from_unit.to_lowercase();
-    let to_unit = to_unit.to_lowercase();
-
-    // Try converting units (this is where fuzziness can help)...
     let valid_units = get_all_unit_strings();
+    let mut messages = vec![];
+    let mut from_parts = vec![];
+    for part in split_parts(left) {
+        let (value_text, unit) = split_value_and_unit(part)?;
+        let unit = normalize_unit(unit);
+        messages.extend(unknown_unit_message(&unit, &valid_units));
+        from_parts.push((value_text, unit));
+    }
 
-    let from_suggestion = if valid_units.contains(&from_unit.as_str()) {
-        None
-    } else {
-        suggest_unit(&from_unit, &valid_units)
-    };
-    let to_suggestion = if valid_units.contains(&to_unit.as_str()) {
-        None
-    } else {
-        suggest_unit(&to_unit, &valid_units)
+    // Only a single value can carry an uncertainty
+    let uncertainty = match from_parts.as_mut_slice() {
+        [(value_text, _)] => {
+            let (value, uncertainty) = split_uncertainty(value_text)?;
+            *value_text = value;
+            uncertainty
+        }
+        _ => None,
     };
+    for (value_text, _) in &from_parts {
+        parse_number(value_text)?;
+    }
 
-    if from_suggestion.is_some() || to_suggestion.is_some() {
-        let mut msg = String::new();
-        if let Some(s) = from_suggestion {
-            msg += &format!("Unknown unit '{}'. Did you mean '{}'?\n", from_unit, s);
+    let to_units: Vec<String> = right
+        .split(',')
+        .map(|to| normalize_unit(to.trim()))
+        .collect();
+    for to_unit in &to_units {
+        if to_unit.is_empty() {
+            return Err(ConvertError::ParseError(
+                "Missing target unit in expression".to_string(),
+            ));
         }
-        if let Some(s) = to_suggestion {
-            msg += &format!("Unknown unit '{}'. Did you mean '{}'?\n", to_unit, s);
+        // Targets that stand for other units are checked when they are resolved
+        let names_units = to_unit == ALL_UNITS
+            || BestUnit::from_str(to_unit).is_ok()
+            || MixedUnit::from_str(to_unit).is_ok();
+        if !names_units {
+            messages.extend(unknown_unit_message(to_unit, &valid_units));
         }
-        return Err(ConvertError::ParseError(msg.trim_end().to_string()));
     }
+    if !messages.is_empty() {
+        return Err(ConvertError::ParseError(messages.join("\n")));
+    }
+
+    // Only sum once every unit is known to be valid
+    let (value, value_text, total, from_unit) = match from_parts.as_slice() {
+        [(value_text, unit)] => (
+            parse_number(value_text)?,
+            value_text.to_string(),
+            None,
+            unit.clone(),
+        ),
+        parts => {
+            let (total, written, unit) = sum_parts(parts)?;
+            (to_f64(&total), written, Some(total), unit)
+        }
+    };
     Ok(ParsedExpression {
         value,
+        value_text,
+        total,
+        uncertainty,
         from: from_unit,
-        to: to_unit,
+        parts: from_parts
+            .into_iter()
+            .map(|(value_text, unit)| (value_text.to_string(), unit))
+            .collect(),
+        to: to_units,
     })
 }
 
-fn parse_value_and_unit(input: &str) -> Result<(f64, String), ConvertError> {
+/// Parses a quantity such as `10.5 km` or `5 ft 11 in`, giving the value and its unit.
+pub(crate) fn parse_value_and_unit(input: &str) -> Result<(f64, String), ConvertError> {
+    let parts = split_parts(input)
+        .into_iter()
+        .map(split_value_and_unit)
+        .collect::<Result<Vec<_>, _>>()?;
+    match parts.as_slice() {
+        [(value_text, unit_str)] => Ok((parse_number(value_text)?, unit_str.to_string())),
+        parts => {
+            let parts: Vec<_> = parts
+                .iter()
+                .map(|(value_text, unit_str)| (*value_text, unit_str.to_string()))
+                .collect();
+            let (total, _, unit_str) = sum_parts(&parts)?;
+            Ok((to_f64(&total), unit_str))
+        }
+    }
+}
+
+/// Splits a quantity written in several units, like `5 ft 11 in`, into one part per unit. A new
+/// part starts wherever a number follows a space after a unit.
+fn split_parts(input: &str) -> Vec<&str> {
+    let input = input.trim();
+    let mut parts = vec![];
+    let mut start = 0;
+    let mut seen_unit = false;
+    let mut previous = ' ';
+    for (i, c) in input.char_indices() {
+        if seen_unit && previous.is_whitespace() && starts_number(&input[i..]) {
+            parts.push(input[start..i].trim());
+            start = i;
+            seen_unit = false;
+        }
+        seen_unit |= c.is_alphabetic() || c == '°';
+        previous = c;
+    }
+    parts.push(input[start..].trim());
+    parts
+}
+
+fn starts_number(s: &str) -> bool {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_digit() => true,
+        Some('.' | '+' | '-') => chars.next().is_some_and(|c| c.is_ascii_digit() || c == '.'),
+        _ => false,
+    }
+}
+
+/// Adds up the parts of a quantity written in several units, exactly, in the unit of the last
+/// part: `5 ft 11 in` is `71 in`. Returns the total, the total as written and its unit. The
+/// written total keeps the decimal places of the last part, which set the precision of the
+/// quantity, so `5 ft 11.50 in` is written `71.50 in` and `1 m 1 ft` is written `4 ft`.
+fn sum_parts(parts: &[(&str, String)]) -> Result<(BigRational, String, String), ConvertError> {
+    let Some((last_value, unit)) = parts.last() else {
+        return Err(ConvertError::ParseError("Missing value".to_string()));
+    };
+    let mut total = BigRational::zero();
+    for (value_text, part_unit) in parts {
+        // Absolute temperatures are points on a scale, so adding them means nothing
+        if let Ok(AnyUnit::Temperature(_)) = AnyUnit::from_str(part_unit) {
+            return Err(ConvertError::ParseError(format!(
+                "Cannot add temperatures in '{}'",
+                part_unit
+            )));
+        }
+        let converter = get_converter(part_unit, unit)?;
+        total += converter.convert_exact(&parse_decimal(value_text)?, part_unit, unit)?;
+    }
+    let places = decimal_places(last_value);
+    let mut written = format_decimal(&Rounding::Decimals(places).apply(&total), DECIMAL_DIGITS);
+    let fraction = written
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len());
+    let missing = places.saturating_sub(fraction);
+    if missing > 0 {
+        if fraction == 0 {
+            written.push('.');
+        }
+        written.push_str(&"0".repeat(missing));
+    }
+    Ok((total, written, unit.clone()))
+}
+
+/// Splits `10.5 km` into the number as written and the unit.
+fn split_value_and_unit(input: &str) -> Result<(&str, &str), ConvertError> {
+    let input = input.trim();
     let (value_str, unit_str) =
-        input
-            .trim()
-            .split_at(
-                input
-                    .find(|c: char| c.is_alphabetic())
-                    .ok_or(ConvertError::ParseError(
-                        "Missing unit in expression".to_string(),
-                    ))?,
-            );
-
-    let value: f64 = value_str
-        .trim()
+        input.split_at(input.find(|c: char| c.is_alphabetic() || c == '°').ok_or(
+            ConvertError::ParseError("Missing unit in expression".to_string()),
+        )?);
+    Ok((value_str.trim(), unit_str.trim()))
+}
+
+/// Splits `10.0 ± 0.2` (or `10.0 +/- 0.2`) into the value as written and the uncertainty.
+fn split_uncertainty(value_str: &str) -> Result<(&str, Option<f64>), ConvertError> {
+    let Some((value, uncertainty)) = value_str
+        .split_once('±')
+        .or_else(|| value_str.split_once("+/-"))
+    else {
+        return Ok((value_str, None));
+    };
+    let uncertainty = parse_number(uncertainty.trim())?;
+    if uncertainty < 0.0 {
+        return Err(ConvertError::ParseError(
+            "Uncertainty must not be negative".to_string(),
+        ));
+    }
+    Ok((value.trim(), Some(uncertainty)))
+}
+
+fn parse_number(value_str: &str) -> Result<f64, ConvertError> {
+    value_str
         .parse()
-        .map_err(|_| ConvertError::ParseError("Invalid number".to_string()))?;
-    Ok((value, unit_str.trim().to_string()))
+        .map_err(|_| ConvertError::ParseError("Invalid number".to_string()))
 }
 
 fn get_all_unit_strings() -> Vec<&'static str> {
@@ -90,9 +283,35 @@ fn get_all_unit_strings() -> Vec<&'static str> {
     units.extend(DistanceUnit::accepted_string());
     units.extend(MassUnit::accepted_string());
     units.extend(TemperatureUnit::accepted_string());
+    units.extend(TimeUnit::accepted_string());
+    units.extend(DerivedUnit::accepted_string());
     units
 }
 
+/// Lower-cases a unit, unless that would change which unit it names: prefix symbols are
+/// case-sensitive, so `Mm` and `mm` are different units, and `NM` is not `nm`.
+fn normalize_unit(unit: &str) -> String {
+    let lower = unit.to_lowercase();
+    match (CompoundUnit::from_str(unit), CompoundUnit::from_str(&lower)) {
+        (Ok(original), Ok(lowered)) if original != lowered => unit.to_string(),
+        (Ok(_), Err(_)) | (Err(_), Ok(_)) => unit.to_string(),
+        _ => lower,
+    }
+}
+
+fn is_valid_unit(unit: &str, valid_units: &[&str]) -> bool {
+    valid_units.contains(&unit) || CompoundUnit::from_str(unit).is_ok()
+}
+
+/// The error for a unit that is not valid but looks like a typo of one that is.
+fn unknown_unit_message(unit: &str, valid_units: &[&str]) -> Option<String> {
+    if is_valid_unit(unit, valid_units) {
+        return None;
+    }
+    suggest_unit(unit, valid_units)
+        .map(|suggestion| format!("Unknown unit '{}'. Did you mean '{}'?", unit, suggestion))
+}
+
 fn suggest_unit(input: &str, valid_units: &[&str]) -> Option<String> {
     let threshold = 2;
     valid_units
@@ -104,7 +323,7 @@ fn suggest_unit(input: &str, valid_units: &[&str]) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
-    use crate::error::ConvertError;
+    use crate::{convert::exact::ratio, error::ConvertError, format::Formatter};
 
     use super::parse_expression;
 
@@ -114,7 +333,16 @@ mod tests {
         let parsed = parse_expression(expr).unwrap();
         assert_eq!(parsed.value, 10.0);
         assert_eq!(parsed.from, "c".to_string());
-        assert_eq!(parsed.to, "f".to_string());
+        assert_eq!(parsed.to, vec!["f".to_string()]);
+    }
+
+    #[test]
+    fn test_parse_expression_several_targets() {
+        let parsed = parse_expression("10 km -> mi, ft,M").unwrap();
+        assert_eq!(parsed.from, "km");
+        assert_eq!(parsed.to, vec!["mi", "ft", "m"]);
+        assert!(parse_expression("10 km -> mi,").is_err());
+        assert!(parse_expression("10 km -> mi, fet").is_err());
     }
 
     #[test]
@@ -132,4 +360,63 @@ mod tests {
         let err = result.unwrap_err();
         assert!(matches!(err, ConvertError::ParseError(_)));
     }
+
+    #[test]
+    fn test_parse_expression_uncertainty() {
+        let parsed = parse_expression("10.0 ± 0.2 in -> mm").unwrap();
+        assert_eq!(parsed.value, 10.0);
+        assert_eq!(parsed.value_text, "10.0");
+        assert_eq!(parsed.uncertainty, Some(0.2));
+        assert_eq!(parsed.from, "in");
+
+        let parsed = parse_expression("10 +/- 0.5 C -> F").unwrap();
+        assert_eq!(parsed.uncertainty, Some(0.5));
+        assert_eq!(parse_expression("10C -> F").unwrap().uncertainty, None);
+        assert!(parse_expression("10 ± -1 C -> F").is_err());
+    }
+
+    #[test]
+    fn test_parse_expression_mixed_units() {
+        let parsed = parse_expression("5 ft 11 in -> cm").unwrap();
+        assert_eq!(parsed.value, 71.0);
+        assert_eq!(parsed.value_text, "71");
+        assert_eq!(parsed.from, "in");
+        assert_eq!(parsed.to, vec!["cm"]);
+        assert_eq!(
+            parsed.mixed_input(&Formatter::default()),
+            Some("5 ft 11 in".to_string())
+        );
+
+        let parsed = parse_expression("5 ft 11.50 in -> cm").unwrap();
+        assert_eq!(parsed.value_text, "71.50");
+
+        // The total is kept exactly, and written to the places of the last part
+        let parsed = parse_expression("1 m 1 ft -> cm").unwrap();
+        assert_eq!(parsed.value_text, "4");
+        assert_eq!(parsed.total, Some(ratio(1631, 381)));
+        assert_eq!(parsed.from, "ft");
+        assert_eq!(
+            parse_expression("10.0 in -> cm")
+                .unwrap()
+                .mixed_input(&Formatter::default()),
+            None
+        );
+
+        let parsed = parse_expression("1 st 3 lb -> kg").unwrap();
+        assert_eq!(parsed.value, 17.0);
+        assert_eq!(parsed.from, "lb");
+
+        let parsed = parse_expression("1 h 30 min 15 s -> min").unwrap();
+        assert_eq!(parsed.value, 5415.0);
+        assert_eq!(parsed.from, "s");
+
+        assert!(matches!(
+            parse_expression("5 ft 3 kg -> m"),
+            Err(ConvertError::IncompatibleDimensions { .. })
+        ));
+        assert!(matches!(
+            parse_expression("10 C 5 F -> K"),
+            Err(ConvertError::ParseError(_))
+        ));
+    }
 }
@@ -0,0 +1,622 @@
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use num_rational::BigRational;
+use num_traits::{Signed, Zero};
+
+use crate::convert::{
+    AnyConverter,
+    exact::{DECIMAL_DIGITS, format_decimal, from_f64, leading_exponent, pow10},
+    temperature::TemperatureDelta,
+};
+
+/// Exponents at or above this are printed in scientific notation by [`Notation::Auto`].
+const SCIENTIFIC_UPPER: i32 = 9;
+/// Exponents at or below this are printed in scientific notation by [`Notation::Auto`], so
+/// `0.00001` is still plain but `0.0000023` is `2.3e-6`.
+const SCIENTIFIC_LOWER: i32 = -6;
+
+/// A value as entered: the text it was written as, which sets the precision of the result,
+/// and for a quantity in several parts their exact total, which the text only approximates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reading<'a> {
+    pub text: &'a str,
+    pub total: Option<&'a BigRational>,
+}
+
+impl<'a> Reading<'a> {
+    /// A single value, which its text gives exactly.
+    pub fn written(text: &'a str) -> Self {
+        Reading { text, total: None }
+    }
+}
+
+/// How a number is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Notation {
+    /// Plain digits, switching to scientific for very large or very small magnitudes
+    #[default]
+    Auto,
+    /// Plain digits, e.g. `1234567.89`
+    Fixed,
+    /// One digit before the point and a power of ten, e.g. `1.23456789e6`
+    Scientific,
+    /// Like scientific, but with the power of ten a multiple of three, e.g. `1.23456789e6` or
+    /// `254e-3`
+    Engineering,
+}
+
+/// How far a displayed value is rounded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    /// Keep this many significant figures, e.g. `6.6` for two.
+    SignificantFigures(usize),
+    /// Keep this many digits after the decimal point, e.g. `6.56` for two.
+    Decimals(usize),
+}
+
+impl Rounding {
+    /// Rounds an exact value half away from zero.
+    pub fn apply(&self, value: &BigRational) -> BigRational {
+        let places = match *self {
+            Rounding::Decimals(places) => places as i32,
+            Rounding::SignificantFigures(figures) => {
+                figures.max(1) as i32 - 1 - leading_exponent_or_zero(value)
+            }
+        };
+        (value * pow10(places)).round() / pow10(places)
+    }
+
+    /// The power of ten of the last digit kept when rounding a value, e.g. `-1` for `25.4`
+    /// rounded to three significant figures.
+    pub fn last_place(&self, value: &BigRational) -> i32 {
+        match *self {
+            Rounding::Decimals(places) => -(places as i32),
+            Rounding::SignificantFigures(figures) => {
+                // Rounding can carry into a new leading digit, as `9.96` does to `10.0`
+                leading_exponent_or_zero(&self.apply(value)) - (figures.max(1) as i32 - 1)
+            }
+        }
+    }
+}
+
+/// Turns values into text for display.
+///
+/// The default lays out the shortest digits that read back as the same `f64`, in
+/// [`Notation::Auto`], with a `.` decimal point and no digit grouping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Formatter {
+    pub notation: Notation,
+    pub rounding: Option<Rounding>,
+    /// Separates groups of three digits before the point, e.g. `,` for `1,234,567.89`.
+    pub group_separator: Option<char>,
+    pub decimal_separator: char,
+}
+
+impl Default for Formatter {
+    fn default() -> Self {
+        Formatter {
+            notation: Notation::Auto,
+            rounding: None,
+            group_separator: None,
+            decimal_separator: '.',
+        }
+    }
+}
+
+impl Formatter {
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+        match (self.rounding, from_f64(value)) {
+            (Some(_), Ok(exact)) => self.format_exact(&exact),
+            _ => self.layout(&Digits::parse(&format!("{:e}", value)).trimmed()),
+        }
+    }
+
+    /// Formats an exact value. Without rounding, results that do not terminate keep
+    /// [`DECIMAL_DIGITS`] significant digits.
+    pub fn format_exact(&self, value: &BigRational) -> String {
+        let digits = match self.rounding {
+            None => Digits::parse(&format_decimal(value, DECIMAL_DIGITS)).trimmed(),
+            Some(Rounding::SignificantFigures(figures)) => Digits::round(value, figures),
+            Some(Rounding::Decimals(places)) => match self.notation {
+                Notation::Auto | Notation::Fixed => Digits::round_to_places(value, places),
+                Notation::Scientific => Digits::round(value, places + 1),
+                Notation::Engineering => {
+                    // The digits before the point depend on the exponent, which rounding
+                    // can carry up (999.96 -> 1.0e3), so settle it first
+                    let figures = |exponent: i32| exponent.rem_euclid(3) as usize + 1 + places;
+                    let rough = Digits::round(value, figures(leading_exponent_or_zero(value)));
+                    Digits::round(value, figures(rough.exponent))
+                }
+            },
+        };
+        // Decimal places are a promise about the fixed layout, so keep to it
+        if matches!(self.rounding, Some(Rounding::Decimals(_))) && self.notation == Notation::Auto {
+            return self.join(digits.fixed());
+        }
+        self.layout(&digits)
+    }
+
+    /// Formats a value rounded to the digit for `10^place`, such as an uncertainty that ends
+    /// at the same digit as the value it belongs to.
+    pub fn format_to_place(&self, value: f64, place: i32) -> String {
+        let Ok(exact) = from_f64(value) else {
+            return self.format(value);
+        };
+        let rounded = (exact / pow10(place)).round() * pow10(place);
+        // Counting figures rather than places lets the notation lay it out like the value
+        let rounding = if rounded.is_zero() {
+            Rounding::Decimals((-place).max(0) as usize)
+        } else {
+            Rounding::SignificantFigures((leading_exponent(&rounded) - place + 1) as usize)
+        };
+        Formatter {
+            rounding: Some(rounding),
+            ..*self
+        }
+        .format_exact(&rounded)
+    }
+
+    /// Lays out a number as it was written, keeping the digits and exponent it was given, so
+    /// that `2.000` stays `2.000` rather than becoming `2` and `12.5` is not echoed as `12.5e0`
+    /// under engineering notation. Only the separators change. Text that is not a number is
+    /// returned as is.
+    pub fn format_written(&self, number: &str) -> String {
+        let Some((_, _, _, exponent)) = parse_decimal_parts(number) else {
+            return number.to_string();
+        };
+        let digits = Digits::parse(number);
+        if !number.contains(['e', 'E']) {
+            return self.join(digits.fixed());
+        }
+        format!("{}e{}", self.join(digits.shifted(exponent)), exponent)
+    }
+
+    fn layout(&self, digits: &Digits) -> String {
+        let scientific = match self.notation {
+            Notation::Auto => {
+                !digits.is_zero()
+                    && (digits.exponent >= SCIENTIFIC_UPPER || digits.exponent <= SCIENTIFIC_LOWER)
+            }
+            Notation::Fixed => false,
+            Notation::Scientific | Notation::Engineering => true,
+        };
+        if !scientific {
+            return self.join(digits.fixed());
+        }
+        let step = if self.notation == Notation::Engineering {
+            3
+        } else {
+            1
+        };
+        let exponent = digits.exponent.div_euclid(step) * step;
+        let (sign, integer, fraction) = digits.shifted(exponent);
+        format!("{}e{}", self.join((sign, integer, fraction)), exponent)
+    }
+
+    fn join(&self, (sign, integer, fraction): (&str, String, String)) -> String {
+        let integer = match self.group_separator {
+            Some(separator) => group(&integer, separator),
+            None => integer,
+        };
+        if fraction.is_empty() {
+            format!("{}{}", sign, integer)
+        } else {
+            format!("{}{}{}{}", sign, integer, self.decimal_separator, fraction)
+        }
+    }
+}
+
+/// Formats a value for display, switching to scientific notation for very large or very
+/// small magnitudes (e.g. astronomical distances) where the plain `f64` output is unreadable.
+pub fn format_value(value: f64) -> String {
+    Formatter::default().format(value)
+}
+
+/// Counts the significant figures of a number as written: `2.0` has two and `0.050` has two.
+/// Trailing zeros of a whole number such as `200` are counted, so that rounding never drops
+/// precision the input may have had.
+pub fn significant_figures(number: &str) -> usize {
+    let mantissa = number.trim().split(['e', 'E']).next().unwrap_or_default();
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    digits.trim_start_matches('0').len().max(1)
+}
+
+/// The rounding justified by a value as written. Most conversions keep its significant
+/// figures, whole numbers included, so `2 m` is `7 ft`. Offset temperature scales and
+/// quantities in several parts keep its absolute precision instead: a reading to the nearest
+/// kelvin is a reading to the nearest 1.8 °F whatever its size, so `-10 K` is `-283 °C`, and
+/// `1 m 1 ft` is measured to the foot, so it is `130 cm`.
+pub fn implied_rounding(
+    converter: &AnyConverter,
+    value: Reading,
+    from: &str,
+    to: &str,
+) -> Rounding {
+    let figures = Rounding::SignificantFigures(significant_figures(value.text));
+    let is_offset = matches!(converter, AnyConverter::Temperature(_))
+        && TemperatureDelta::from_str(from).is_err();
+    if !is_offset && value.total.is_none() {
+        return figures;
+    }
+    let last_place = 10f64.powi(-(decimal_places(value.text) as i32));
+    match converter.convert_uncertainty(last_place, from, to) {
+        Ok(step) if step > 0.0 => Rounding::Decimals((-step.log10().floor()).max(0.0) as usize),
+        _ => figures,
+    }
+}
+
+/// Counts the digits after the decimal point of a number as written, e.g. two for `1.25`
+/// and four for `1.25e-2`.
+pub(crate) fn decimal_places(number: &str) -> usize {
+    let (mantissa, exponent) = number
+        .trim()
+        .split_once(['e', 'E'])
+        .unwrap_or((number.trim(), "0"));
+    let fraction = mantissa
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.len());
+    (fraction as i64 - exponent.parse::<i64>().unwrap_or(0)).max(0) as usize
+}
+
+/// Inserts a separator between groups of three digits, e.g. `1234567` is `1,234,567`.
+fn group(integer: &str, separator: char) -> String {
+    let mut grouped = String::new();
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i).is_multiple_of(3) {
+            grouped.push(separator);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+fn leading_exponent_or_zero(value: &BigRational) -> i32 {
+    if value.is_zero() {
+        0
+    } else {
+        leading_exponent(value)
+    }
+}
+
+/// Splits a decimal such as `-1.50e3` into its sign, integer digits, fraction digits and
+/// exponent, or `None` if it is not one.
+fn parse_decimal_parts(number: &str) -> Option<(bool, String, String, i32)> {
+    let number = number.trim().replace('_', "");
+    let (mantissa, exponent) = match number.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse().ok()?),
+        None => (number.as_str(), 0),
+    };
+    let negative = mantissa.starts_with('-');
+    let unsigned = mantissa.trim_start_matches(['+', '-']);
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if integer.len() + fraction.len() == 0 || !is_digits(integer) || !is_digits(fraction) {
+        return None;
+    }
+    Some((
+        negative,
+        integer.to_string(),
+        fraction.to_string(),
+        exponent,
+    ))
+}
+
+/// A number as its significant digits `d.ddd` and the power of ten of the first one. Zero
+/// keeps its digits too, with the exponent marking the last place shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Digits {
+    negative: bool,
+    digits: String,
+    exponent: i32,
+}
+
+impl Digits {
+    /// Reads the digits of a decimal, keeping trailing zeros.
+    fn parse(number: &str) -> Digits {
+        let Some((negative, integer, fraction, exponent)) = parse_decimal_parts(number) else {
+            return Digits::zero(0);
+        };
+        let all = format!("{}{}", integer, fraction);
+        let significant = all.trim_start_matches('0');
+        if significant.is_empty() {
+            return Digits::zero(exponent - fraction.len() as i32);
+        }
+        let leading_zeros = (all.len() - significant.len()) as i32;
+        Digits {
+            negative,
+            digits: significant.to_string(),
+            exponent: integer.len() as i32 - 1 - leading_zeros + exponent,
+        }
+    }
+
+    /// Rounds half away from zero to the given number of significant figures.
+    fn round(value: &BigRational, figures: usize) -> Digits {
+        let figures = figures.max(1);
+        if value.is_zero() {
+            return Digits {
+                negative: false,
+                digits: "0".repeat(figures),
+                exponent: 0,
+            };
+        }
+        let mut exponent = leading_exponent(value);
+        let mut rounded = (value.abs() * pow10(figures as i32 - 1 - exponent)).round();
+        if rounded >= pow10(figures as i32) {
+            rounded /= pow10(1);
+            exponent += 1;
+        }
+        Digits {
+            negative: value.is_negative(),
+            digits: rounded.to_integer().to_string(),
+            exponent,
+        }
+    }
+
+    /// Rounds half away from zero to the given number of decimal places.
+    fn round_to_places(value: &BigRational, places: usize) -> Digits {
+        let rounded = (value.abs() * pow10(places as i32)).round().to_integer();
+        if rounded.is_zero() {
+            return Digits::zero(-(places as i32));
+        }
+        let digits = rounded.to_string();
+        Digits {
+            negative: value.is_negative(),
+            exponent: digits.len() as i32 - 1 - places as i32,
+            digits,
+        }
+    }
+
+    fn zero(last_place: i32) -> Digits {
+        Digits {
+            negative: false,
+            digits: "0".to_string(),
+            exponent: last_place,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.digits.chars().all(|c| c == '0')
+    }
+
+    /// Drops trailing zeros, for digits that say nothing about precision.
+    fn trimmed(mut self) -> Digits {
+        if self.is_zero() {
+            return Digits::zero(0);
+        }
+        let trimmed = self.digits.trim_end_matches('0').len();
+        self.digits.truncate(trimmed);
+        self
+    }
+
+    /// The sign, integer digits and fraction digits when written without an exponent.
+    fn fixed(&self) -> (&'static str, String, String) {
+        if self.is_zero() {
+            let places = (self.digits.len() as i32 - 1 - self.exponent).max(0) as usize;
+            return ("", "0".to_string(), "0".repeat(places));
+        }
+        self.shifted(0)
+    }
+
+    /// The sign, integer digits and fraction digits of the number divided by `10^exponent`.
+    fn shifted(&self, exponent: i32) -> (&'static str, String, String) {
+        let sign = if self.negative && !self.is_zero() {
+            "-"
+        } else {
+            ""
+        };
+        // The number of digits before the point
+        let point = self.exponent - exponent + 1;
+        let length = self.digits.len() as i32;
+        let (integer, fraction) = if point <= 0 {
+            let zeros = "0".repeat(-point as usize);
+            ("0".to_string(), format!("{}{}", zeros, self.digits))
+        } else if point >= length {
+            let zeros = "0".repeat((point - length) as usize);
+            (format!("{}{}", self.digits, zeros), String::new())
+        } else {
+            let (integer, fraction) = self.digits.split_at(point as usize);
+            (integer.to_string(), fraction.to_string())
+        };
+        (sign, integer, fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::convert::{
+        exact::{decimal, ratio},
+        get_converter,
+    };
+
+    use super::{
+        Formatter, Notation, Reading, Rounding, format_value, implied_rounding, significant_figures,
+    };
+
+    #[test]
+    fn test_format_value() {
+        assert_eq!(format_value(6.5), "6.5");
+        assert_eq!(format_value(0.0), "0");
+        assert_eq!(format_value(-0.25), "-0.25");
+        assert_eq!(format_value(1_000_000.0), "1000000");
+        assert_eq!(format_value(9.4607304725808e15), "9.4607304725808e15");
+        assert_eq!(format_value(1.5e-16), "1.5e-16");
+        // Either side of the lower boundary
+        assert_eq!(format_value(0.000015), "0.000015");
+        assert_eq!(format_value(2.3132729624838294e-6), "2.3132729624838294e-6");
+        assert_eq!(format_value(1e-6), "1e-6");
+    }
+
+    #[test]
+    fn test_format_rounded() {
+        let rounded = |value: f64, rounding: Rounding| {
+            Formatter {
+                rounding: Some(rounding),
+                ..Formatter::default()
+            }
+            .format(value)
+        };
+        let feet = 6.561679790026247;
+        assert_eq!(rounded(feet, Rounding::SignificantFigures(1)), "7");
+        assert_eq!(rounded(feet, Rounding::SignificantFigures(2)), "6.6");
+        assert_eq!(rounded(feet, Rounding::SignificantFigures(4)), "6.562");
+        assert_eq!(rounded(feet, Rounding::Decimals(2)), "6.56");
+        assert_eq!(rounded(254.0, Rounding::SignificantFigures(4)), "254.0");
+        assert_eq!(rounded(1609.344, Rounding::SignificantFigures(2)), "1600");
+        assert_eq!(rounded(9.96, Rounding::SignificantFigures(2)), "10");
+        assert_eq!(rounded(0.0, Rounding::SignificantFigures(2)), "0.0");
+        assert_eq!(rounded(0.004, Rounding::Decimals(2)), "0.00");
+        assert_eq!(
+            rounded(9.4607304725808e15, Rounding::SignificantFigures(3)),
+            "9.46e15"
+        );
+        assert_eq!(rounded(1.5e-16, Rounding::SignificantFigures(2)), "1.5e-16");
+    }
+
+    #[test]
+    fn test_apply_rounding() {
+        assert_eq!(
+            Rounding::SignificantFigures(3).apply(&decimal("70.866")),
+            decimal("70.9")
+        );
+        assert_eq!(
+            Rounding::Decimals(1).apply(&decimal("-0.25")),
+            decimal("-0.3")
+        );
+        assert_eq!(
+            Rounding::SignificantFigures(2).apply(&decimal("0")),
+            decimal("0")
+        );
+    }
+
+    #[test]
+    fn test_last_place() {
+        let sig_figs = Rounding::SignificantFigures(3);
+        assert_eq!(sig_figs.last_place(&decimal("25.4")), -1);
+        assert_eq!(sig_figs.last_place(&decimal("9.996")), -1);
+        assert_eq!(sig_figs.last_place(&decimal("32808")), 2);
+        assert_eq!(Rounding::Decimals(2).last_place(&decimal("1234.5")), -2);
+
+        let formatter = Formatter::default();
+        assert_eq!(formatter.format_to_place(0.508, -1), "0.5");
+        assert_eq!(formatter.format_to_place(0.5, -2), "0.50");
+        assert_eq!(formatter.format_to_place(61.0, 2), "100");
+        assert_eq!(formatter.format_to_place(8.4e-10, -10), "8e-10");
+    }
+
+    #[test]
+    fn test_notations() {
+        let formatted = |value: f64, notation: Notation| {
+            Formatter {
+                notation,
+                ..Formatter::default()
+            }
+            .format(value)
+        };
+        assert_eq!(formatted(1234567.89, Notation::Fixed), "1234567.89");
+        assert_eq!(formatted(1.5e-16, Notation::Fixed), "0.00000000000000015");
+        assert_eq!(formatted(1234567.89, Notation::Scientific), "1.23456789e6");
+        assert_eq!(formatted(0.254, Notation::Scientific), "2.54e-1");
+        assert_eq!(formatted(12345.0, Notation::Engineering), "12.345e3");
+        assert_eq!(formatted(0.254, Notation::Engineering), "254e-3");
+        assert_eq!(formatted(-0.0254, Notation::Engineering), "-25.4e-3");
+        assert_eq!(formatted(0.0, Notation::Engineering), "0e0");
+
+        let engineering = Formatter {
+            notation: Notation::Engineering,
+            rounding: Some(Rounding::Decimals(1)),
+            ..Formatter::default()
+        };
+        assert_eq!(engineering.format(999.96), "1.0e3");
+        assert_eq!(engineering.format(12345.0), "12.3e3");
+    }
+
+    #[test]
+    fn test_grouping_and_separators() {
+        let european = Formatter {
+            group_separator: Some('.'),
+            decimal_separator: ',',
+            ..Formatter::default()
+        };
+        assert_eq!(european.format(1234567.89), "1.234.567,89");
+        assert_eq!(european.format(-123.5), "-123,5");
+        let grouped = Formatter {
+            group_separator: Some(','),
+            ..Formatter::default()
+        };
+        assert_eq!(grouped.format(1234567.89), "1,234,567.89");
+        assert_eq!(grouped.format(999.0), "999");
+        assert_eq!(grouped.format_exact(&decimal("1609.344")), "1,609.344");
+    }
+
+    #[test]
+    fn test_format_written() {
+        let formatter = Formatter {
+            group_separator: Some(','),
+            ..Formatter::default()
+        };
+        assert_eq!(formatter.format_written("2.000"), "2.000");
+        assert_eq!(formatter.format_written("0.050"), "0.050");
+        assert_eq!(formatter.format_written("1234567.0"), "1,234,567.0");
+        assert_eq!(formatter.format_written("1.5e20"), "1.5e20");
+        assert_eq!(formatter.format_written("-0"), "0");
+        assert_eq!(formatter.format_written("0.000001"), "0.000001");
+
+        let engineering = Formatter {
+            notation: Notation::Engineering,
+            ..Formatter::default()
+        };
+        assert_eq!(engineering.format_written("12.5"), "12.5");
+        assert_eq!(engineering.format_written("15e2"), "15e2");
+    }
+
+    #[test]
+    fn test_significant_figures() {
+        assert_eq!(significant_figures("2"), 1);
+        assert_eq!(significant_figures("2.0"), 2);
+        assert_eq!(significant_figures("2.000"), 4);
+        assert_eq!(significant_figures("0.050"), 2);
+        assert_eq!(significant_figures("-1.50e3"), 3);
+        assert_eq!(significant_figures("200"), 3);
+        assert_eq!(significant_figures("0"), 1);
+    }
+
+    #[test]
+    fn test_implied_rounding() {
+        let rounding = |value: &str, from: &str, to: &str| {
+            let converter = get_converter(from, to).unwrap();
+            implied_rounding(&converter, Reading::written(value), from, to)
+        };
+        assert_eq!(rounding("2", "m", "ft"), Rounding::SignificantFigures(1));
+        assert_eq!(rounding("2.0", "m", "ft"), Rounding::SignificantFigures(2));
+        assert_eq!(rounding("2e3", "m", "ft"), Rounding::SignificantFigures(1));
+        assert_eq!(rounding("-10", "K", "C"), Rounding::Decimals(0));
+        assert_eq!(rounding("-10.0", "K", "C"), Rounding::Decimals(1));
+        assert_eq!(rounding("37.0", "C", "F"), Rounding::Decimals(1));
+        assert_eq!(rounding("1.5", "dC", "dF"), Rounding::SignificantFigures(2));
+
+        // `1 m 1 ft` totals 4.28 ft, measured to the foot
+        let total = ratio(1631, 381);
+        let mixed = Reading {
+            text: "4",
+            total: Some(&total),
+        };
+        let converter = get_converter("ft", "cm").unwrap();
+        assert_eq!(
+            implied_rounding(&converter, mixed, "ft", "cm"),
+            Rounding::Decimals(0)
+        );
+        let converter = get_converter("ft", "m").unwrap();
+        let mixed = Reading {
+            text: "4.3",
+            ..mixed
+        };
+        assert_eq!(
+            implied_rounding(&converter, mixed, "ft", "m"),
+            Rounding::Decimals(2)
+        );
+    }
+}
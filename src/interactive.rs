@@ -1,6 +1,17 @@
 use std::io::{self, Write};
 
-use crate::{convert::get_converter, error::ConvertError, expression::parse_expression};
+use crate::{
+    convert::{
+        ALL_UNITS, all_targets,
+        best::resolve_target,
+        exact::{check_finite, from_f64, parse_float},
+        get_converter,
+        mixed::MixedUnit,
+    },
+    error::ConvertError,
+    expression::parse_expression,
+    format::{Formatter, Rounding},
+};
 
 pub fn run_interactive() -> Result<(), ConvertError> {
     println!("🔁 Welcome to the Unit Converter! Type 'quit' to exit.");
@@ -28,9 +39,16 @@ pub fn run_interactive() -> Result<(), ConvertError> {
         } else {
             match parse_expression(trimmed) {
                 Ok(expression) => {
-                    match run_conversion(expression.value, &expression.from, &expression.to) {
-                        Ok(_) => continue,
-                        Err(e) => eprintln!("❌ Something went wrong! The error was: {}", e),
+                    for to in &expression.to {
+                        if let Err(e) = run_conversion(
+                            expression.value,
+                            expression.uncertainty,
+                            &expression.from,
+                            expression.mixed_input(&Formatter::default()).as_deref(),
+                            to,
+                        ) {
+                            eprintln!("❌ Something went wrong! The error was: {}", e);
+                        }
                     }
                 }
                 Err(e) => eprintln!("❌ Failed to parse expression: {}", e),
@@ -41,13 +59,11 @@ pub fn run_interactive() -> Result<(), ConvertError> {
 }
 
 fn guided_prompt() -> Result<(), ConvertError> {
-    let value = prompt("Enter value to convert:")?
-        .parse::<f64>()
-        .map_err(|_| ConvertError::ParseError("Invalid number".to_string()))?;
+    let value = parse_float(&prompt("Enter value to convert:")?)?;
     let from = prompt("Enter FROM unit (e.g. m, kg, C,...):")?;
     let to = prompt("Enter TO unit (e.g. m, kg, C,...):")?;
 
-    run_conversion(value, &from, &to)?;
+    run_conversion(value, None, &from, None, &to)?;
     Ok(())
 }
 
@@ -59,15 +75,64 @@ fn prompt(msg: &str) -> Result<String, ConvertError> {
     Ok(input.trim().to_string())
 }
 
-fn run_conversion(value: f64, from: &str, to: &str) -> Result<(), ConvertError> {
+/// Converts a value and prints the result. A quantity entered in several units is echoed as
+/// `mixed_input`, as it was written.
+fn run_conversion(
+    value: f64,
+    uncertainty: Option<f64>,
+    from: &str,
+    mixed_input: Option<&str>,
+    to: &str,
+) -> Result<(), ConvertError> {
+    let value = check_finite(value)?;
+    if to == ALL_UNITS {
+        for to in all_targets(from)? {
+            run_conversion(value, uncertainty, from, mixed_input, &to)?;
+        }
+        return Ok(());
+    }
+    let target = resolve_target(value, from, to)?;
+    let mixed = MixedUnit::parse_target(&target)?;
+    let to = &match &mixed {
+        Some(mixed) => mixed.smallest().to_string(),
+        None => target,
+    };
     let converter = get_converter(from, to)?;
     let result = converter.convert(value, from, to)?;
-    println!(
-        "✅ {} {} = {} {}",
-        value,
-        converter.get_unit_string(from),
-        result,
-        converter.get_unit_string(to),
-    );
+    let formatter = Formatter::default();
+    let result = match &mixed {
+        // Split the float as the digits it carries, not its exact binary value
+        Some(mixed) => {
+            let formatter = Formatter {
+                rounding: Some(Rounding::SignificantFigures(f64::DIGITS as usize)),
+                ..formatter
+            };
+            mixed.format(&from_f64(result)?, &formatter)
+        }
+        None => formatter.format(result),
+    };
+    let unit = converter.get_unit_string(to);
+    let (value, result) = match uncertainty {
+        Some(uncertainty) => (
+            format!(
+                "{} ± {}",
+                formatter.format(value),
+                formatter.format(uncertainty)
+            ),
+            format!(
+                "{} ± {} {}",
+                result,
+                formatter.format(converter.convert_uncertainty(uncertainty, from, to)?),
+                unit
+            ),
+        ),
+        None if mixed.is_some() => (formatter.format(value), result),
+        None => (formatter.format(value), format!("{} {}", result, unit)),
+    };
+    let value = match mixed_input {
+        Some(written) => written.to_string(),
+        None => format!("{} {}", value, converter.get_unit_string(from)),
+    };
+    println!("✅ {} = {}", value, result);
     Ok(())
 }
@@ -2,5 +2,7 @@ pub mod cli;
 pub mod convert;
 pub mod error;
 pub mod expression;
+pub mod format;
 pub mod interactive;
+pub mod quantity;
 pub mod test_utils;
@@ -1,11 +1,23 @@
 use clap::Parser;
+use strum::IntoEnumIterator;
 use unitconvert::{
-    cli::{Cli, Commands},
+    cli::{Cli, Commands, ConversionOptions, PrecisionMode},
     convert::{
-        UnitConverter, distance::DistanceConverter, get_converter, mass::MassConverter,
+        ALL_UNITS, AnyConverter, UnitConverter, all_targets,
+        best::resolve_target,
+        definitions::describe,
+        derived::DerivedUnit,
+        distance::DistanceConverter,
+        exact::{check_finite, from_f64, parse_decimal, parse_float, to_f64},
+        get_converter,
+        mass::MassConverter,
+        mixed::MixedUnit,
         temperature::TemperatureConverter,
+        time::TimeConverter,
     },
-    expression::parse_expression,
+    error::ConvertError,
+    expression::{ParsedExpression, parse_expression},
+    format::{Formatter, Reading, Rounding},
     interactive,
 };
 
@@ -14,49 +26,97 @@ fn main() {
 
     match &cli.command {
         Commands::Convert(args) => {
+            if args.all {
+                println!(
+                    "Converting {} from {} to every unit...",
+                    args.value, args.from
+                );
+                if let Err(e) = print_all(
+                    Reading::written(&args.value),
+                    None,
+                    &args.from,
+                    None,
+                    &args.options,
+                ) {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+                return;
+            }
             println!(
                 "Converting {} from {} to  {}...",
-                args.value, args.from, args.to
+                args.value,
+                args.from,
+                args.to.join(", ")
             );
-            let converter = get_converter(&args.from, &args.to).unwrap_or_else(|e| {
-                println!("Error: {}", e);
+            let value = parse_decimal(&args.value).unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
                 std::process::exit(1);
             });
-
-            match converter.convert(args.value, &args.from, &args.to) {
-                Ok(result) => {
-                    println!(
-                        "{} {} = {} {}",
-                        args.value,
-                        converter.get_unit_string(&args.from),
-                        result,
-                        converter.get_unit_string(&args.to)
-                    )
-                }
-                Err(e) => {
+            for to in &args.to {
+                let (to, mixed) =
+                    conversion_target(to_f64(&value), &args.from, to).unwrap_or_else(|e| {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    });
+                let converter = get_converter(&args.from, &to).unwrap_or_else(|e| {
                     eprintln!("Error: {}", e);
                     std::process::exit(1);
+                });
+
+                let result = convert_value(
+                    &converter,
+                    Reading::written(&args.value),
+                    None,
+                    &args.from,
+                    &to,
+                    mixed.as_ref(),
+                    &args.options,
+                );
+                match result {
+                    Ok((value, result)) => {
+                        println!(
+                            "{} {} = {}",
+                            value,
+                            converter.get_unit_string(&args.from),
+                            result
+                        )
+                    }
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    }
                 }
             }
         }
         Commands::Expression(args) => match parse_expression(&args.expr) {
-            Ok(expression) => match get_converter(&expression.from, &expression.to) {
-                Ok(converter) => {
-                    match converter.convert(expression.value, &expression.from, &expression.to) {
-                        Ok(result) => println!(
-                            "{} {} = {} {}",
-                            expression.value,
-                            converter.get_unit_string(&expression.from),
-                            result,
-                            converter.get_unit_string(&expression.to)
-                        ),
-                        Err(e) => eprintln!("Conversion error: {}", e),
+            Ok(expression) => {
+                for to in &expression.to {
+                    if to == ALL_UNITS {
+                        let result = print_all(
+                            expression.reading(),
+                            expression.uncertainty,
+                            &expression.from,
+                            expression.mixed_input(&args.options.plain_formatter()),
+                            &args.options,
+                        );
+                        if let Err(e) = result {
+                            eprintln!("Conversion error: {}", e);
+                        }
+                    } else {
+                        convert_expression(&expression, to, &args.options);
                     }
                 }
-                Err(e) => eprintln!("Unsupported conversion: {}", e),
-            },
+            }
             Err(e) => eprintln!("Failed to parse expression: {}", e),
         },
+        Commands::Info(args) => match describe(&args.unit) {
+            Ok(description) => println!("{}", description),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        },
         Commands::Interactive => {
             if interactive::run_interactive().is_err() {
                 eprintln!("An error occurred. The program will now quit");
@@ -82,6 +142,16 @@ fn main() {
                             println!("{}", unit);
                         }
                     }
+                    "time" | "s" => {
+                        for unit in TimeConverter.supported_units() {
+                            println!("{}", unit);
+                        }
+                    }
+                    "derived" | "n" => {
+                        for unit in DerivedUnit::iter() {
+                            println!("{}", unit);
+                        }
+                    }
                     other => {
                         eprintln!("Unknown unit type: '{}'", other);
                         std::process::exit(1);
@@ -90,10 +160,209 @@ fn main() {
             }
             None => {
                 println!("Supported unit types:");
-                println!(" - distance");
-                println!(" - mass");
-                println!(" - temperature");
+                println!(" - distance (d)");
+                println!(" - mass (m)");
+                println!(" - temperature (t)");
+                println!(" - time (s)");
+                println!(" - derived (n), which take SI prefixes like kN or MW");
             }
         },
     }
 }
+
+/// Converts a parsed expression into one of its targets and prints the result.
+fn convert_expression(expression: &ParsedExpression, to: &str, options: &ConversionOptions) {
+    let (to, mixed) = match conversion_target(expression.value, &expression.from, to) {
+        Ok(target) => target,
+        Err(e) => return eprintln!("Failed to parse expression: {}", e),
+    };
+    match get_converter(&expression.from, &to) {
+        Ok(converter) => {
+            let result = convert_value(
+                &converter,
+                expression.reading(),
+                expression.uncertainty,
+                &expression.from,
+                &to,
+                mixed.as_ref(),
+                options,
+            );
+            match result {
+                Ok((value, result)) => {
+                    let value = expression
+                        .mixed_input(&options.plain_formatter())
+                        .unwrap_or_else(|| {
+                            format!("{} {}", value, converter.get_unit_string(&expression.from))
+                        });
+                    println!("{} = {}", value, result)
+                }
+                Err(e) => eprintln!("Conversion error: {}", e),
+            }
+        }
+        Err(e) => eprintln!("Unsupported conversion: {}", e),
+    }
+}
+
+/// Resolves a target as written into the unit to convert into and, for targets like `ft+in`,
+/// the units to break the result into.
+fn conversion_target(
+    value: f64,
+    from: &str,
+    to: &str,
+) -> Result<(String, Option<MixedUnit>), ConvertError> {
+    let to = resolve_target(value, from, to)?;
+    match MixedUnit::parse_target(&to)? {
+        Some(mixed) => Ok((mixed.smallest().to_string(), Some(mixed))),
+        None => Ok((to, None)),
+    }
+}
+
+/// Converts a value as written on the command line, returning the value and the result with
+/// its unit, formatted for display and each followed by its uncertainty if one was given.
+fn convert_value(
+    converter: &AnyConverter,
+    value: Reading,
+    uncertainty: Option<f64>,
+    from: &str,
+    to: &str,
+    mixed: Option<&MixedUnit>,
+    options: &ConversionOptions,
+) -> Result<(String, String), ConvertError> {
+    let (value, result, place) = convert_reading(converter, value, from, to, mixed, options)?;
+    let unit = converter.get_unit_string(to);
+    match (uncertainty, mixed) {
+        (Some(uncertainty), _) => {
+            let (uncertainty, converted) =
+                convert_uncertainty(converter, uncertainty, from, to, place, options)?;
+            Ok((
+                format!("{} ± {}", value, uncertainty),
+                format!("{} ± {} {}", result, converted, unit),
+            ))
+        }
+        // A mixed result already names its units
+        (None, Some(_)) => Ok((value, result)),
+        (None, None) => Ok((value, format!("{} {}", result, unit))),
+    }
+}
+
+/// Converts an uncertainty, returning it before and after, formatted for display. The
+/// converted uncertainty ends at `place`, the last digit kept in the result it belongs to, so
+/// that `10.0 ± 0.2 in` is `25.4 ± 0.5 cm`.
+fn convert_uncertainty(
+    converter: &AnyConverter,
+    uncertainty: f64,
+    from: &str,
+    to: &str,
+    place: Option<i32>,
+    options: &ConversionOptions,
+) -> Result<(String, String), ConvertError> {
+    let converted = converter.convert_uncertainty(uncertainty, from, to)?;
+    let formatter = Formatter {
+        rounding: options.decimals.map(Rounding::Decimals),
+        ..options.plain_formatter()
+    };
+    let converted = match place {
+        Some(place) => formatter.format_to_place(converted, place),
+        None => formatter.format(converted),
+    };
+    Ok((formatter.format(uncertainty), converted))
+}
+
+/// Prints a value converted into every unit of its kind, as a table with the results lined up.
+/// A quantity entered in several units is headed by `mixed_input`, as it was written.
+fn print_all(
+    value: Reading,
+    uncertainty: Option<f64>,
+    from: &str,
+    mixed_input: Option<String>,
+    options: &ConversionOptions,
+) -> Result<(), ConvertError> {
+    let mut rows = vec![];
+    for to in all_targets(from)? {
+        let converter = get_converter(from, &to)?;
+        let (_, result, place) = convert_reading(&converter, value, from, &to, None, options)?;
+        let result = match uncertainty {
+            Some(uncertainty) => {
+                let (_, converted) =
+                    convert_uncertainty(&converter, uncertainty, from, &to, place, options)?;
+                format!("{} ± {}", result, converted)
+            }
+            None => result,
+        };
+        rows.push((result, converter.get_unit_string(&to)));
+    }
+
+    let converter = get_converter(from, from)?;
+    let mut value = options.plain_formatter().format_written(value.text);
+    if let Some(uncertainty) = uncertainty {
+        let (uncertainty, _) =
+            convert_uncertainty(&converter, uncertainty, from, from, None, options)?;
+        value = format!("{} ± {}", value, uncertainty);
+    }
+    let value =
+        mixed_input.unwrap_or_else(|| format!("{} {}", value, converter.get_unit_string(from)));
+    println!("{} =", value);
+    let width = rows
+        .iter()
+        .map(|(result, _)| result.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (result, unit) in rows {
+        println!("  {:>width$} {}", result, unit);
+    }
+    Ok(())
+}
+
+/// Converts a value as written, returning the value and the result formatted for display,
+/// along with the power of ten of the last digit the result was rounded to, if it was.
+fn convert_reading(
+    converter: &AnyConverter,
+    value: Reading,
+    from: &str,
+    to: &str,
+    mixed: Option<&MixedUnit>,
+    options: &ConversionOptions,
+) -> Result<(String, String, Option<i32>), ConvertError> {
+    let formatter = options.formatter(converter, value, from, to);
+    // Show the value as written, since its trailing zeros set the precision
+    let written = options.plain_formatter().format_written(value.text);
+    let (result, exact) = match options.precision_mode {
+        PrecisionMode::Float => {
+            let value = match value.total {
+                Some(total) => check_finite(to_f64(total))?,
+                None => parse_float(value.text)?,
+            };
+            let result = if options.allow_below_absolute_zero {
+                converter.convert_unchecked(value, from, to)?
+            } else {
+                converter.convert(value, from, to)?
+            };
+            let text = match mixed {
+                Some(mixed) => mixed.format(&from_f64(result)?, &formatter),
+                None => formatter.format(result),
+            };
+            (text, from_f64(result).ok())
+        }
+        PrecisionMode::Decimal => {
+            let value = match value.total {
+                Some(total) => total.clone(),
+                None => parse_decimal(value.text)?,
+            };
+            let result = if options.allow_below_absolute_zero {
+                converter.convert_exact_unchecked(&value, from, to)?
+            } else {
+                converter.convert_exact(&value, from, to)?
+            };
+            let text = match mixed {
+                Some(mixed) => mixed.format(&result, &formatter),
+                None => formatter.format_exact(&result),
+            };
+            (text, Some(result))
+        }
+    };
+    let place = formatter
+        .rounding
+        .zip(exact)
+        .map(|(rounding, exact)| rounding.last_place(&exact));
+    Ok((written, result, place))
+}
@@ -0,0 +1,452 @@
+use std::{
+    cmp::Ordering,
+    fmt::Display,
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::FromStr,
+};
+
+use crate::{
+    convert::{AnyUnit, compound::CompoundUnit, exact::scale, get_converter},
+    error::ConvertError,
+    expression::parse_value_and_unit,
+    format::format_value,
+};
+
+/// A value together with the unit it is measured in, e.g. `10 km`.
+///
+/// The unit is parsed once, when the quantity is made, so a quantity always holds a unit
+/// the converters understand and displays it in its canonical form (`"meters"` as `m`).
+///
+/// Quantities compare by what they measure rather than how it is written, so
+/// `1 mi > 1500 m` and `1 kg == 1000 g`. Quantities of different dimensions are neither
+/// equal nor ordered, and neither are an absolute temperature and a temperature difference
+/// (`0 °C` against `5 Δ°C`). This lets a `Vec<Quantity>` of one dimension be sorted with
+/// `sort_by(|a, b| a.partial_cmp(b).unwrap())`.
+#[derive(Debug, Clone)]
+pub struct Quantity {
+    value: f64,
+    unit: CompoundUnit,
+}
+
+impl Quantity {
+    /// Creates a quantity, checking that the unit is one the converters understand.
+    pub fn new(value: f64, unit: &str) -> Result<Self, ConvertError> {
+        Ok(Quantity {
+            value,
+            unit: CompoundUnit::from_str(unit)?,
+        })
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn unit(&self) -> &CompoundUnit {
+        &self.unit
+    }
+
+    /// Converts the quantity into another unit of the same dimension.
+    pub fn to(&self, unit: &str) -> Result<Quantity, ConvertError> {
+        self.to_unit(&CompoundUnit::from_str(unit)?)
+    }
+
+    /// Converts into a unit, refusing to turn an absolute temperature into a temperature
+    /// difference or back, as the temperature converter does.
+    fn to_unit(&self, unit: &CompoundUnit) -> Result<Quantity, ConvertError> {
+        let (from, to) = (self.unit.to_string(), unit.to_string());
+        match (
+            self.is_absolute_temperature(),
+            is_absolute_temperature(unit),
+        ) {
+            // Temperature scales have offsets, so they go through the temperature converter
+            (true, true) => {
+                let value = get_converter(&from, &to)?.convert(self.value, &from, &to)?;
+                Ok(Quantity {
+                    value,
+                    unit: unit.clone(),
+                })
+            }
+            (true, false) | (false, true) if self.unit.dimension() == unit.dimension() => {
+                Err(ConvertError::UnsupportedConversion(from, to))
+            }
+            _ => self.rescale(unit),
+        }
+    }
+
+    /// Scales the value into another unit of the same dimension, ignoring any offset.
+    fn rescale(&self, unit: &CompoundUnit) -> Result<Quantity, ConvertError> {
+        if self.unit.dimension() != unit.dimension() {
+            return Err(ConvertError::IncompatibleDimensions {
+                from: self.unit.to_string(),
+                from_dimension: self.unit.dimension(),
+                to: unit.to_string(),
+                to_dimension: unit.dimension(),
+            });
+        }
+        Ok(Quantity {
+            value: scale(
+                self.value,
+                &(self.unit.exact_factor() / unit.exact_factor()),
+            ),
+            unit: unit.clone(),
+        })
+    }
+
+    /// Adds two quantities of the same dimension, giving the result in this quantity's
+    /// unit: `5 ft + 3 in` is `5.25 ft`.
+    ///
+    /// An absolute temperature is a point on a scale, so only a temperature difference can be
+    /// added to it: `5 °C + 3 Δ°F` is fine, but `5 °C + 3 °F` is an error.
+    pub fn checked_add(&self, rhs: &Quantity) -> Result<Quantity, ConvertError> {
+        // A difference moves an absolute temperature by its size in degrees of that scale
+        let rhs = rhs.as_difference()?.rescale(&self.unit)?;
+        Ok(Quantity {
+            value: self.value + rhs.value,
+            unit: self.unit.clone(),
+        })
+    }
+
+    /// Subtracts a quantity of the same dimension, giving the result in this quantity's unit.
+    /// As with [`Self::checked_add`], an absolute temperature cannot be subtracted.
+    pub fn checked_sub(&self, rhs: &Quantity) -> Result<Quantity, ConvertError> {
+        let rhs = rhs.as_difference()?.rescale(&self.unit)?;
+        Ok(Quantity {
+            value: self.value - rhs.value,
+            unit: self.unit.clone(),
+        })
+    }
+
+    /// Multiplies two quantities, combining their units: `2 m × 3 m` is `6 m²`.
+    ///
+    /// A quantity of the same dimension is first converted into this quantity's unit, so
+    /// `2 m × 50 cm` is `1 m²` rather than `100 m·cm`.
+    pub fn checked_mul(&self, rhs: &Quantity) -> Result<Quantity, ConvertError> {
+        let rhs = self.reconcile(rhs)?;
+        Ok(Quantity {
+            value: self.value * rhs.value,
+            unit: (self.unit.clone() * rhs.unit)?,
+        })
+    }
+
+    /// Divides two quantities, combining their units: `100 km ÷ 2 h` is `50 km/h`.
+    pub fn checked_div(&self, rhs: &Quantity) -> Result<Quantity, ConvertError> {
+        let rhs = self.reconcile(rhs)?;
+        Ok(Quantity {
+            value: self.value / rhs.value,
+            unit: (self.unit.clone() / rhs.unit)?,
+        })
+    }
+
+    /// Whether two quantities are equal to within `tolerance`, measured in this quantity's
+    /// unit. Quantities of different dimensions are never approximately equal.
+    pub fn approx_eq(&self, other: &Quantity, tolerance: f64) -> bool {
+        other
+            .to_unit(&self.unit)
+            .is_ok_and(|other| (self.value - other.value).abs() <= tolerance)
+    }
+
+    /// `rhs` in this quantity's unit if the two share a dimension, otherwise unchanged.
+    /// Absolute temperatures on different scales are refused, as converting one would move
+    /// its zero.
+    fn reconcile(&self, rhs: &Quantity) -> Result<Quantity, ConvertError> {
+        if rhs.unit.dimension() != self.unit.dimension() || rhs.unit == self.unit {
+            return Ok(rhs.clone());
+        }
+        if self.is_absolute_temperature() || rhs.is_absolute_temperature() {
+            return Err(ConvertError::ParseError(format!(
+                "Cannot combine the temperatures {} and {}",
+                self, rhs
+            )));
+        }
+        rhs.to_unit(&self.unit)
+    }
+
+    /// The quantity, if it can be added to or subtracted from another.
+    fn as_difference(&self) -> Result<&Quantity, ConvertError> {
+        if self.is_absolute_temperature() {
+            return Err(ConvertError::ParseError(format!(
+                "Cannot add or subtract the temperature {}; use a difference such as Δ{}",
+                self, self.unit
+            )));
+        }
+        Ok(self)
+    }
+
+    fn is_absolute_temperature(&self) -> bool {
+        is_absolute_temperature(&self.unit)
+    }
+}
+
+/// Whether a unit is a point on a temperature scale, like `°C`, rather than a difference.
+fn is_absolute_temperature(unit: &CompoundUnit) -> bool {
+    matches!(unit.as_simple(), Some(AnyUnit::Temperature(_)))
+}
+
+impl PartialEq for Quantity {
+    fn eq(&self, other: &Quantity) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Quantity {
+    /// Compares in this quantity's unit. Quantities that cannot be converted into each
+    /// other, including a temperature and a temperature difference, are unordered.
+    fn partial_cmp(&self, other: &Quantity) -> Option<Ordering> {
+        let other = other.to_unit(&self.unit).ok()?;
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+macro_rules! impl_quantity_op {
+    ($op:ident, $method:ident, $checked:ident) => {
+        impl $op<&Quantity> for &Quantity {
+            type Output = Result<Quantity, ConvertError>;
+
+            fn $method(self, rhs: &Quantity) -> Self::Output {
+                self.$checked(rhs)
+            }
+        }
+
+        impl $op for Quantity {
+            type Output = Result<Quantity, ConvertError>;
+
+            fn $method(self, rhs: Quantity) -> Self::Output {
+                self.$checked(&rhs)
+            }
+        }
+    };
+}
+
+impl_quantity_op!(Add, add, checked_add);
+impl_quantity_op!(Sub, sub, checked_sub);
+impl_quantity_op!(Mul, mul, checked_mul);
+impl_quantity_op!(Div, div, checked_div);
+
+impl Mul<f64> for Quantity {
+    type Output = Quantity;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Quantity {
+            value: self.value * rhs,
+            unit: self.unit,
+        }
+    }
+}
+
+impl Mul<Quantity> for f64 {
+    type Output = Quantity;
+
+    fn mul(self, rhs: Quantity) -> Self::Output {
+        rhs * self
+    }
+}
+
+impl Div<f64> for Quantity {
+    type Output = Quantity;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        Quantity {
+            value: self.value / rhs,
+            unit: self.unit,
+        }
+    }
+}
+
+impl Neg for Quantity {
+    type Output = Quantity;
+
+    fn neg(self) -> Self::Output {
+        Quantity {
+            value: -self.value,
+            unit: self.unit,
+        }
+    }
+}
+
+impl FromStr for Quantity {
+    type Err = ConvertError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, unit) = parse_value_and_unit(s)?;
+        Quantity::new(value, &unit)
+    }
+}
+
+impl Display for Quantity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} {}", precision, self.value, self.unit),
+            None => write!(f, "{} {}", format_value(self.value), self.unit),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::{error::ConvertError, test_utils::assert_approx_eq};
+
+    use super::Quantity;
+
+    #[test]
+    fn test_parse_and_display() {
+        let quantity = Quantity::from_str("10 km").unwrap();
+        assert_eq!(quantity.value(), 10.0);
+        assert_eq!(quantity.unit().to_string(), "km");
+        assert_eq!(quantity.to_string(), "10 km");
+
+        let quantity = Quantity::from_str("9.81 meters/s^2").unwrap();
+        assert_eq!(quantity.to_string(), "9.81 m/s²");
+        assert_eq!(format!("{:.1}", quantity), "9.8 m/s²");
+
+        let quantity = Quantity::from_str("5 ft 11 in").unwrap();
+        assert_eq!(quantity.to_string(), "71 in");
+    }
+
+    #[test]
+    fn test_convert_quantity() {
+        let distance = Quantity::new(10.0, "km").unwrap();
+        let miles = distance.to("mi").unwrap();
+        assert_approx_eq(miles.value(), 6.21371, 1e-5);
+        assert_eq!(miles.unit().to_string(), "mi");
+
+        let temperature = Quantity::from_str("100 C").unwrap().to("F").unwrap();
+        assert_approx_eq(temperature.value(), 212.0, 1e-9);
+        assert_eq!(temperature.unit().to_string(), "°F");
+    }
+
+    #[test]
+    fn test_temperature_differences_are_not_temperatures() {
+        let difference = Quantity::from_str("10 dC").unwrap();
+        assert_eq!(
+            difference.to("F"),
+            Err(ConvertError::UnsupportedConversion(
+                "Δ°C".to_string(),
+                "°F".to_string()
+            ))
+        );
+        assert!(matches!(
+            Quantity::from_str("20 C").unwrap().to("dK"),
+            Err(ConvertError::UnsupportedConversion(_, _))
+        ));
+        assert_approx_eq(difference.to("dF").unwrap().value(), 18.0, 1e-9);
+    }
+
+    #[test]
+    fn test_invalid_quantities() {
+        assert_eq!(
+            Quantity::new(1.0, "banana"),
+            Err(ConvertError::InvalidUnit("banana".to_string()))
+        );
+        let err = Quantity::from_str("1 m").unwrap().to("kg").unwrap_err();
+        assert!(matches!(err, ConvertError::IncompatibleDimensions { .. }));
+    }
+
+    #[test]
+    fn test_add_and_subtract() {
+        let height = Quantity::from_str("5 ft").unwrap();
+        let sum = (&height + &Quantity::from_str("3 in").unwrap()).unwrap();
+        assert_approx_eq(sum.value(), 5.25, 1e-9);
+        assert_eq!(sum.unit().to_string(), "ft");
+
+        let difference =
+            (Quantity::from_str("1 km").unwrap() - Quantity::from_str("250 m").unwrap()).unwrap();
+        assert_approx_eq(difference.value(), 0.75, 1e-9);
+        assert_eq!(difference.unit().to_string(), "km");
+
+        let err = (&height + &Quantity::from_str("3 kg").unwrap()).unwrap_err();
+        assert!(matches!(err, ConvertError::IncompatibleDimensions { .. }));
+    }
+
+    #[test]
+    fn test_temperature_arithmetic() {
+        let celsius = Quantity::from_str("5 C").unwrap();
+        let warmer = (&celsius + &Quantity::from_str("9 delta F").unwrap()).unwrap();
+        assert_approx_eq(warmer.value(), 10.0, 1e-9);
+        assert_eq!(warmer.unit().to_string(), "°C");
+
+        for other in ["3 F", "3 C", "3 K"] {
+            let other = Quantity::from_str(other).unwrap();
+            assert!(matches!(
+                &celsius + &other,
+                Err(ConvertError::ParseError(_))
+            ));
+            assert!(matches!(
+                &celsius - &other,
+                Err(ConvertError::ParseError(_))
+            ));
+        }
+        assert!(matches!(
+            &celsius * &Quantity::from_str("3 F").unwrap(),
+            Err(ConvertError::ParseError(_))
+        ));
+        let squared = (&celsius * &celsius).unwrap();
+        assert_eq!(squared.to_string(), "25 °C²");
+    }
+
+    #[test]
+    fn test_multiply_and_divide() {
+        let side = Quantity::from_str("2 m").unwrap();
+        let area = (&side * &Quantity::from_str("3 m").unwrap()).unwrap();
+        assert_eq!(area.to_string(), "6 m²");
+
+        let area = (&side * &Quantity::from_str("50 cm").unwrap()).unwrap();
+        assert_approx_eq(area.value(), 1.0, 1e-9);
+        assert_eq!(area.unit().to_string(), "m²");
+
+        let speed =
+            (Quantity::from_str("100 km").unwrap() / Quantity::from_str("2 h").unwrap()).unwrap();
+        assert_eq!(speed.to_string(), "50 km/h");
+
+        let ratio = (&side / &Quantity::from_str("50 cm").unwrap()).unwrap();
+        assert_approx_eq(ratio.value(), 4.0, 1e-9);
+        assert_eq!(ratio.unit().to_string(), "1");
+    }
+
+    #[test]
+    fn test_scalar_operations() {
+        let mass = Quantity::from_str("3 kg").unwrap();
+        assert_eq!((mass.clone() * 2.0).to_string(), "6 kg");
+        assert_eq!((2.0 * mass.clone()).to_string(), "6 kg");
+        assert_eq!((mass.clone() / 2.0).to_string(), "1.5 kg");
+        assert_eq!((-mass).to_string(), "-3 kg");
+    }
+
+    #[test]
+    fn test_compare_across_units() {
+        let mile = Quantity::from_str("1 mi").unwrap();
+        assert!(mile > Quantity::from_str("1500 m").unwrap());
+        assert!(Quantity::from_str("1 kg").unwrap() == Quantity::from_str("1000 g").unwrap());
+        assert!(Quantity::from_str("0 C").unwrap() > Quantity::from_str("30 F").unwrap());
+
+        let kilogram = Quantity::from_str("1 kg").unwrap();
+        assert_eq!(mile.partial_cmp(&kilogram), None);
+        assert_ne!(mile, kilogram);
+
+        let freezing = Quantity::from_str("0 C").unwrap();
+        let difference = Quantity::from_str("5 delta C").unwrap();
+        assert_eq!(freezing.partial_cmp(&difference), None);
+        assert_eq!(difference.partial_cmp(&freezing), None);
+    }
+
+    #[test]
+    fn test_sort_mixed_units() {
+        let mut weights: Vec<Quantity> = ["2 lb", "1 kg", "500 g", "40 oz"]
+            .iter()
+            .map(|s| Quantity::from_str(s).unwrap())
+            .collect();
+        weights.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let sorted: Vec<String> = weights.iter().map(Quantity::to_string).collect();
+        assert_eq!(sorted, ["500 g", "2 lb", "1 kg", "40 oz"]);
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let foot = Quantity::from_str("1 ft").unwrap();
+        assert!(foot.approx_eq(&Quantity::from_str("12 in").unwrap(), 1e-9));
+        assert!(foot.approx_eq(&Quantity::from_str("30 cm").unwrap(), 0.02));
+        assert!(!foot.approx_eq(&Quantity::from_str("30 cm").unwrap(), 0.001));
+        assert!(!foot.approx_eq(&Quantity::from_str("1 kg").unwrap(), 1.0));
+    }
+}
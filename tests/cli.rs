@@ -20,9 +20,7 @@ mod cli {
     fn valid_conversion_should_work() {
         let mut cmd = Command::cargo_bin("unitconvert").unwrap();
         cmd.args(["convert", "--from", "m", "--to", "ft", "2"]);
-        cmd.assert()
-            .success()
-            .stdout(contains("2 m = 6.561679790026246 ft"));
+        cmd.assert().success().stdout(contains("2 m = 7 ft"));
     }
 
     #[test]
@@ -33,8 +31,8 @@ mod cli {
         match cli.command {
             Commands::Convert(args) => {
                 assert_eq!(args.from, "m");
-                assert_eq!(args.to, "ft");
-                assert_eq!(args.value, 2.0);
+                assert_eq!(args.to, vec!["ft"]);
+                assert_eq!(args.value, "2");
             }
             _ => panic!("Expected Convert subcommand"),
         }
@@ -54,4 +52,368 @@ mod cli {
             .success()
             .stdout(contains("10 °C = "));
     }
+
+    #[test]
+    fn below_absolute_zero_fails_unless_allowed() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "k", "--to", "c", "--", "-10"])
+            .assert()
+            .failure()
+            .stderr(contains("below absolute zero"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args([
+            "convert",
+            "--from",
+            "k",
+            "--to",
+            "c",
+            "--allow-below-absolute-zero",
+            "--",
+            "-10",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("-10 K = -283 °C"));
+    }
+
+    #[test]
+    fn decimal_precision_mode_is_exact() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args([
+            "convert",
+            "--from",
+            "ft",
+            "--to",
+            "in",
+            "--precision-mode",
+            "decimal",
+            "0.1",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("0.1 ft = 1.2 in"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args([
+            "expression",
+            "--expr",
+            "2.2 lb -> kg",
+            "--precision-mode",
+            "decimal",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("2.2 lb = 0.997903214 kg"));
+    }
+
+    #[test]
+    fn info_shows_definition_and_source() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["info", "lb"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("0.45359237 kg").and(contains("NIST")));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["info", "ftUS"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("Exactly:    1 ftUS = 1200/3937 m"));
+    }
+
+    #[test]
+    fn expression_carries_uncertainty() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "10.0 ± 0.2 in -> cm"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("10.0 ± 0.2 in = 25.4 ± 0.5 cm"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args([
+            "expression",
+            "--expr",
+            "10.0 ± 0.2 in -> mm",
+            "--decimals",
+            "2",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(contains("10.0 ± 0.20 in = 254.00 ± 5.08 mm"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "20 ± 0.5 C -> F"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("20 ± 0.5 °C = 68 ± 1 °F"));
+    }
+
+    #[test]
+    fn help_describes_each_subcommand() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.arg("--help");
+        cmd.assert().success().stdout(
+            contains("Convert a value from one unit into others")
+                .and(contains("Show a unit's exact definition and its source"))
+                .and(contains("Options shared").not()),
+        );
+    }
+
+    #[test]
+    fn rounding_follows_input_precision_unless_overridden() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "m", "--to", "ft", "2.000"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("2.000 m = 6.562 ft"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args([
+            "convert",
+            "--from",
+            "m",
+            "--to",
+            "ft",
+            "--sig-figs",
+            "3",
+            "2",
+        ]);
+        cmd.assert().success().stdout(contains("2 m = 6.56 ft"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "2 m -> ft", "--decimals", "4"]);
+        cmd.assert().success().stdout(contains("2 m = 6.5617 ft"));
+    }
+
+    #[test]
+    fn output_formatting_options() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "km", "--to", "m", "--group-digits"]);
+        cmd.args(["--decimal-separator", ",", "1234.5"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("1.234,5 km = 1.234.500 m"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "km", "--to", "m"]);
+        cmd.args(["--decimal-separator", " ", "1234.5"]);
+        cmd.assert()
+            .failure()
+            .stderr(contains("invalid value ' ' for '--decimal-separator"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args([
+            "expression",
+            "--expr",
+            "12.5 km -> m",
+            "--notation",
+            "engineering",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(contains("12.5 km = 12.5e3 m"));
+    }
+
+    #[test]
+    fn auto_target_picks_readable_unit() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "0.000032 km -> auto"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("0.000032 km = 3.2 cm"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "min", "--to", "best", "90"]);
+        cmd.assert().success().stdout(contains("90 min = 1.5 h"));
+    }
+
+    #[test]
+    fn all_table_switches_to_scientific_consistently() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "mi", "--all", "1.000"]);
+        cmd.assert().success().stdout(
+            contains(" 2.313e-6 R☉")
+                .and(contains(" 1.076e-8 au"))
+                .and(contains(" 0.0002523 R⊕")),
+        );
+    }
+
+    #[test]
+    fn all_lists_units_built_from_others() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "m/s", "--all", "10"]);
+        cmd.assert()
+            .success()
+            .stdout(contains(" 600 m/min").and(contains(" 0.010 km/s")));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "N", "--all", "1500"]);
+        cmd.assert()
+            .success()
+            .stdout(contains(" 1.500 kN").and(contains(" 1500000 mN")));
+    }
+
+    #[test]
+    fn list_categories() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["list", "--category", "s"]);
+        cmd.assert().success().stdout(contains("min"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["list", "--category", "derived"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("N\n").and(contains("Pa")));
+    }
+
+    #[test]
+    fn mixed_unit_input_is_summed() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "5 ft 11 in -> cm"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("5 ft 11 in = 180 cm"));
+
+        // The trailing zero of the last part still counts towards the precision
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "5 ft 11.50 in -> cm"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("5 ft 11.50 in = 181.61 cm"));
+
+        // A total that does not terminate keeps the precision of the parts as written
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "1 m 1 ft -> cm, ft+in"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("1 m 1 ft = 130 cm\n").and(contains("1 m 1 ft = 4 ft 3 in\n")));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args([
+            "expression",
+            "--expr",
+            "1 m 1 ft -> cm",
+            "--precision-mode",
+            "decimal",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(contains("1 m 1 ft = 130.48 cm"));
+    }
+
+    #[test]
+    fn mixed_unit_targets() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "180.3 cm -> ft+in"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("180.3 cm = 5 ft 10.98 in"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "kg", "--to", "st+lb", "84.0"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("84.0 kg = 13 st 3 lb"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "5415 s -> h:min:s"]);
+        cmd.assert().success().stdout(contains("5415 s = 1:30:15"));
+    }
+
+    #[test]
+    fn several_targets() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "10.0 km -> mi, ft, m"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("10.0 km = 6.21 mi"))
+            .stdout(contains("10.0 km = 32800 ft"))
+            .stdout(contains("10.0 km = 10000 m"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args([
+            "convert", "--from", "kg", "--to", "lb", "--to", "g,oz", "2.00",
+        ]);
+        cmd.assert()
+            .success()
+            .stdout(contains("2.00 kg = 4.41 lb"))
+            .stdout(contains("2.00 kg = 2000 g"))
+            .stdout(contains("2.00 kg = 70.5 oz"));
+    }
+
+    #[test]
+    fn all_units_table() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "5.000", "--from", "kg", "--all"]);
+        cmd.assert()
+            .success()
+            .stdout(contains("5.000 kg =\n"))
+            .stdout(contains("     11.02 lb\n"))
+            .stdout(contains("      5000 g\n"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["expression", "--expr", "100 C -> all"]);
+        cmd.assert().success().stdout(contains("212 °F"));
+
+        let result = Cli::try_parse_from([
+            "unitconvert",
+            "convert",
+            "5",
+            "--from",
+            "kg",
+            "--to",
+            "lb",
+            "--all",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn out_of_range_numbers_are_rejected() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "m", "--to", "ft", "1e9999999"]);
+        cmd.timeout(std::time::Duration::from_secs(5))
+            .assert()
+            .failure()
+            .stderr(contains("Exponents must be between"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "m", "--to", "ft", "1e400"]);
+        cmd.assert()
+            .failure()
+            .stderr(contains("1e400 is out of range for float mode"));
+    }
+
+    #[test]
+    fn invalid_numbers_are_rejected_up_front() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "1_000", "--from", "m", "--to", "ft"]);
+        cmd.assert()
+            .failure()
+            .stderr(contains("invalid value '1_000'").and(contains("Invalid number")));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", " 2.5", "--from", "m", "--to", "cm"]);
+        cmd.assert().success().stdout(contains("2.5 m = 250 cm"));
+    }
+
+    #[test]
+    fn errors_go_to_stderr() {
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "m", "--to", "bogus", "2"]);
+        cmd.assert()
+            .failure()
+            .stdout(contains("Error").not())
+            .stderr(contains("Error: Invalid unit: 'bogus'"));
+
+        let mut cmd = Command::cargo_bin("unitconvert").unwrap();
+        cmd.args(["convert", "--from", "m", "--to", "kg", "2"]);
+        cmd.assert()
+            .failure()
+            .stdout(contains("Error").not())
+            .stderr(contains(
+                "Error: Cannot convert 'm' (length) to 'kg' (mass)",
+            ));
+    }
 }
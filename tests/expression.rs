@@ -6,7 +6,7 @@ fn parses_valid_expression() {
     let result = parse_expression("10C -> F").unwrap();
     assert_eq!(result.value, 10.0);
     assert_eq!(result.from, "c".to_string());
-    assert_eq!(result.to, "f".to_string());
+    assert_eq!(result.to, vec!["f".to_string()]);
 }
 
 #[test]
@@ -24,3 +24,59 @@ fn handles_non_numeric_input() {
     let err = result.unwrap_err();
     assert!(matches!(err, ConvertError::ParseError(_)));
 }
+
+#[test]
+fn parses_temperature_difference() {
+    let result = parse_expression("10 Δ°C -> delta F").unwrap();
+    assert_eq!(result.value, 10.0);
+    assert_eq!(result.from, "δ°c".to_string());
+    assert_eq!(result.to, vec!["delta f".to_string()]);
+}
+
+#[test]
+fn parses_compound_units() {
+    let result = parse_expression("9.81 m/s² -> ft/s^2").unwrap();
+    assert_eq!(result.value, 9.81);
+    assert_eq!(result.from, "m/s²".to_string());
+    assert_eq!(result.to, vec!["ft/s^2".to_string()]);
+}
+
+#[test]
+fn keeps_case_of_prefixed_units() {
+    let result = parse_expression("5 Mg -> kg").unwrap();
+    assert_eq!(result.from, "Mg".to_string());
+    let result = parse_expression("5 kg -> MILLIGRAMS").unwrap();
+    assert_eq!(result.to, vec!["milligrams".to_string()]);
+    assert!(parse_expression("5 MG -> kg").is_err());
+    assert!(parse_expression("10 NM -> km").is_err());
+}
+
+#[test]
+fn parses_unit_powers() {
+    let result = parse_expression("1 m^3 -> ft**3").unwrap();
+    assert_eq!(result.value, 1.0);
+    assert_eq!(result.from, "m^3".to_string());
+    assert_eq!(result.to, vec!["ft**3".to_string()]);
+}
+
+#[test]
+fn parses_auto_target() {
+    let result = parse_expression("86400 s -> auto imperial").unwrap();
+    assert_eq!(result.to, vec!["auto imperial".to_string()]);
+    assert!(parse_expression("86400 s -> atuo").is_err());
+}
+
+#[test]
+fn parses_mixed_unit_input() {
+    let result = parse_expression("3 lb 4 oz -> g").unwrap();
+    assert_eq!(result.value, 52.0);
+    assert_eq!(result.from, "oz".to_string());
+    assert_eq!(result.to, vec!["g".to_string()]);
+}
+
+#[test]
+fn parses_several_targets() {
+    let result = parse_expression("10 km to mi, auto imperial, ft+in").unwrap();
+    assert_eq!(result.value, 10.0);
+    assert_eq!(result.to, vec!["mi", "auto imperial", "ft+in"]);
+}